@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::time::{Duration, Instant};
+
+use crate::days::registry;
+use crate::days::solver::Day;
+
+const USAGE: &str = "usage: bench [--iterations N] [--day D] [--out FILE] [--baseline FILE]";
+
+/// Where the summary is written unless `--out` says otherwise.
+const OUTPUT: &str = "bench_output.txt";
+
+struct Options {
+    iterations: usize,
+    day: Option<u32>,
+    out: String,
+    baseline: Option<String>,
+}
+
+impl Options {
+    fn from_args(args: &[String]) -> Result<Self, Error> {
+        let mut options = Self {
+            iterations: 10,
+            day: None,
+            out: OUTPUT.to_owned(),
+            baseline: None,
+        };
+
+        let invalid = |msg: String| Error::new(ErrorKind::InvalidInput, format!("{msg}\n{USAGE}"));
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| invalid(format!("missing value for {arg}")))
+            };
+
+            match arg.as_str() {
+                "--iterations" | "-n" => {
+                    let v = value()?;
+                    options.iterations = v
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| invalid(format!("invalid iteration count {v:?}")))?;
+                }
+                "--day" | "-d" => {
+                    let v = value()?;
+                    let day = v
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid day {v:?}")))?;
+                    options.day = Some(day);
+                }
+                "--out" | "-o" => options.out = value()?.clone(),
+                "--baseline" | "-b" => options.baseline = Some(value()?.clone()),
+                _ => return Err(invalid(format!("unknown argument {arg:?}"))),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Fastest, median and slowest of a set of runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    pub fn from_runs(runs: &[Duration]) -> Option<Self> {
+        let mut runs = runs.to_vec();
+        runs.sort();
+
+        let n = runs.len();
+        let median = match n {
+            0 => return None,
+            _ if n % 2 == 1 => runs[n / 2],
+            _ => (runs[n / 2 - 1] + runs[n / 2]) / 2,
+        };
+
+        Some(Self {
+            min: runs[0],
+            median,
+            max: runs[n - 1],
+        })
+    }
+}
+
+/// One line of the summary: the timing of a stage of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Record {
+    day: u32,
+    stage: &'static str,
+    iterations: usize,
+    timing: Timing,
+}
+
+impl Record {
+    const HEADER: &'static str = "day,stage,iterations,min_ns,median_ns,max_ns";
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.day,
+            self.stage,
+            self.iterations,
+            self.timing.min.as_nanos(),
+            self.timing.median.as_nanos(),
+            self.timing.max.as_nanos()
+        )
+    }
+}
+
+/// Median nanoseconds by `(day, stage)` from a summary written earlier.
+fn read_baseline(path: &str) -> Result<HashMap<(u32, String), u128>, Error> {
+    let mut medians = HashMap::new();
+
+    for line in fs::read_to_string(path)?.lines().skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        if let [day, stage, _, _, median, _] = fields.as_slice() {
+            if let (Ok(day), Ok(median)) = (day.parse(), median.parse()) {
+                medians.insert((day, stage.to_string()), median);
+            }
+        }
+    }
+
+    Ok(medians)
+}
+
+/// Times the parse, part 1 and part 2 of each day separately, skipping the
+/// days whose input is not there yet.
+fn measure(days: &[Day], iterations: usize) -> Result<Vec<Record>, Error> {
+    let mut records = Vec::new();
+
+    for day in days {
+        if !day.has_input() {
+            println!("day {:02}: no input at {}", day.number, day.input);
+            continue;
+        }
+
+        let input = day.read_input()?;
+        let mut runs = [Vec::new(), Vec::new(), Vec::new()];
+
+        for _ in 0..iterations {
+            let now = Instant::now();
+            let solver = day.parse(&input)?;
+            runs[0].push(now.elapsed());
+
+            let now = Instant::now();
+            solver.part1();
+            runs[1].push(now.elapsed());
+
+            let now = Instant::now();
+            solver.part2();
+            runs[2].push(now.elapsed());
+        }
+
+        for (stage, runs) in ["parse", "part1", "part2"].into_iter().zip(runs.iter()) {
+            records.push(Record {
+                day: day.number,
+                stage,
+                iterations,
+                timing: Timing::from_runs(runs).unwrap(),
+            });
+        }
+    }
+
+    Ok(records)
+}
+
+/// Runs the parse, part 1 and part 2 of every registered day separately,
+/// prints their timings and writes them as CSV.
+pub fn main(args: &[String]) -> Result<(), Error> {
+    let options = Options::from_args(args)?;
+    let baseline = match &options.baseline {
+        Some(path) => read_baseline(path)?,
+        None => HashMap::new(),
+    };
+
+    let days: Vec<_> = registry()
+        .into_iter()
+        .filter(|d| options.day.is_none_or(|n| n == d.number))
+        .collect();
+    if days.is_empty() {
+        return Err(Error::new(ErrorKind::NotFound, "no such day"));
+    }
+
+    let records = measure(&days, options.iterations)?;
+
+    for record in records.iter() {
+        let timing = record.timing;
+        let change = match baseline.get(&(record.day, record.stage.to_owned())) {
+            Some(old) if *old > 0 => {
+                let new = timing.median.as_nanos() as f64;
+                format!(" ({:+.1}%)", (new / *old as f64 - 1.0) * 100.0)
+            }
+            _ => String::new(),
+        };
+
+        println!(
+            "day {:02} {:<5}  min {:>12?}  median {:>12?}  max {:>12?}{}",
+            record.day, record.stage, timing.min, timing.median, timing.max, change
+        );
+    }
+
+    let mut summary = String::from(Record::HEADER);
+    summary.push('\n');
+    for record in records.iter() {
+        summary.push_str(&record.to_csv());
+        summary.push('\n');
+    }
+    fs::write(&options.out, summary)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timing() {
+        let ms = Duration::from_millis;
+
+        let t = Timing::from_runs(&[ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!(
+            t,
+            Timing {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            }
+        );
+
+        let t = Timing::from_runs(&[ms(4), ms(1), ms(2), ms(9)]).unwrap();
+        assert_eq!(t.median, ms(3));
+
+        assert!(Timing::from_runs(&[]).is_none());
+    }
+
+    #[test]
+    fn test_options() {
+        let args: Vec<String> = ["-n", "3", "--day", "4"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let options = Options::from_args(&args).unwrap();
+        assert_eq!(options.iterations, 3);
+        assert_eq!(options.day, Some(4));
+        assert_eq!(options.out, OUTPUT);
+
+        let args = vec!["--iterations".to_owned(), "0".to_owned()];
+        assert!(Options::from_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_day_without_input() {
+        let day = registry().into_iter().find(|d| d.number == 6).unwrap();
+        let mut scaffolded = day;
+        scaffolded.number = 25;
+        scaffolded.input = "puzzle_inputs/day25/puzzle.input";
+        assert!(!scaffolded.has_input());
+
+        let records = measure(&[day, scaffolded], 1).unwrap();
+        let days: Vec<u32> = records.iter().map(|r| r.day).collect();
+        assert_eq!(days, [6, 6, 6]);
+    }
+}
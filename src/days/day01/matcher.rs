@@ -0,0 +1,190 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::scanner::WORDS;
+
+/// A vocabulary entry found in the text. `start..end` is the byte range of
+/// the match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    next: HashMap<u8, usize>,
+    fail: usize,
+    /// Indices of the patterns ending at this node, including the ones
+    /// reached through failure links.
+    outputs: Vec<usize>,
+}
+
+/// Aho–Corasick automaton over a vocabulary of `(pattern, value)` pairs.
+///
+/// Built once, it finds every occurrence of every pattern, overlaps
+/// included, in a single pass over the text.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    nodes: Vec<Node>,
+    patterns: Vec<(String, u32)>,
+}
+
+impl Matcher {
+    pub fn new<S: AsRef<str>>(vocabulary: &[(S, u32)]) -> Self {
+        let mut nodes = vec![Node::default()];
+        let mut patterns = Vec::new();
+
+        for (pattern, value) in vocabulary {
+            let pattern = pattern.as_ref();
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut state = 0;
+            for b in pattern.bytes() {
+                state = match nodes[state].next.get(&b) {
+                    Some(&s) => s,
+                    None => {
+                        nodes.push(Node::default());
+                        let s = nodes.len() - 1;
+                        nodes[state].next.insert(b, s);
+                        s
+                    }
+                };
+            }
+
+            nodes[state].outputs.push(patterns.len());
+            patterns.push((pattern.to_owned(), *value));
+        }
+
+        let mut queue: VecDeque<usize> = nodes[0].next.values().cloned().collect();
+        while let Some(state) = queue.pop_front() {
+            let edges: Vec<(u8, usize)> = nodes[state].next.iter().map(|(b, s)| (*b, *s)).collect();
+
+            for (b, child) in edges {
+                let mut f = nodes[state].fail;
+                while f != 0 && !nodes[f].next.contains_key(&b) {
+                    f = nodes[f].fail;
+                }
+                let fail = match nodes[f].next.get(&b) {
+                    Some(&s) if s != child => s,
+                    _ => 0,
+                };
+
+                nodes[child].fail = fail;
+                let inherited = nodes[fail].outputs.clone();
+                nodes[child].outputs.extend(inherited);
+
+                queue.push_back(child);
+            }
+        }
+
+        Self { nodes, patterns }
+    }
+
+    /// The numerals "0" to "9".
+    pub fn numerals() -> Self {
+        let vocabulary: Vec<(String, u32)> = (0..10).map(|n| (n.to_string(), n)).collect();
+        Self::new(&vocabulary)
+    }
+
+    /// The numerals plus the spelled-out digits "one" to "nine".
+    pub fn english() -> Self {
+        let mut vocabulary: Vec<(String, u32)> = (0..10).map(|n| (n.to_string(), n)).collect();
+        for (n, w) in WORDS.iter().enumerate() {
+            vocabulary.push((w.to_string(), n as u32 + 1));
+        }
+        Self::new(&vocabulary)
+    }
+
+    /// Every match in `text`, ordered by end position and, for matches
+    /// ending at the same byte, longest first.
+    pub fn find_all(&self, text: &str) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut state = 0;
+
+        for (i, b) in text.bytes().enumerate() {
+            while state != 0 && !self.nodes[state].next.contains_key(&b) {
+                state = self.nodes[state].fail;
+            }
+            state = self.nodes[state].next.get(&b).cloned().unwrap_or(0);
+
+            for p in self.nodes[state].outputs.iter() {
+                let (pattern, value) = &self.patterns[*p];
+                matches.push(Match {
+                    start: i + 1 - pattern.len(),
+                    end: i + 1,
+                    value: *value,
+                });
+            }
+        }
+
+        matches
+    }
+
+    /// The leftmost and the rightmost match of `text`. Among matches with
+    /// the same start, the longest wins.
+    pub fn first_and_last(&self, text: &str) -> Option<(Match, Match)> {
+        let matches = self.find_all(text);
+
+        let first = matches
+            .iter()
+            .min_by_key(|m| (m.start, usize::MAX - m.end))?;
+        let last = matches.iter().max_by_key(|m| (m.start, m.end))?;
+
+        Some((*first, *last))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_all_overlapping() {
+        let m = Matcher::english();
+        let values: Vec<(usize, u32)> = m
+            .find_all("xtwone3four")
+            .iter()
+            .map(|m| (m.start, m.value))
+            .collect();
+        assert_eq!(values, vec![(1, 2), (3, 1), (6, 3), (7, 4)]);
+    }
+
+    #[test]
+    fn test_custom_vocabulary() {
+        let m = Matcher::new(&[("zero", 0), ("un", 1), ("deux", 2), ("vingt et un", 21)]);
+
+        let found: Vec<u32> = m
+            .find_all("zerodeux vingt et un")
+            .iter()
+            .map(|m| m.value)
+            .collect();
+        assert_eq!(found, vec![0, 2, 21, 1]);
+
+        let (first, last) = m.first_and_last("deux vingt et un").unwrap();
+        assert_eq!((first.value, last.value), (2, 1));
+        assert_eq!((last.start, last.end), (14, 16));
+    }
+
+    #[test]
+    fn test_suffix_patterns() {
+        let m = Matcher::new(&[("he", 1), ("she", 2), ("his", 3), ("hers", 4)]);
+        let found: Vec<(usize, usize)> = m
+            .find_all("ushers")
+            .iter()
+            .map(|m| (m.start, m.end))
+            .collect();
+        assert_eq!(found, vec![(1, 4), (2, 4), (2, 6)]);
+    }
+
+    #[test]
+    fn test_longest_first_match() {
+        let m = Matcher::new(&[("seven", 7), ("seventeen", 17)]);
+        let (first, last) = m.first_and_last("seventeen").unwrap();
+        assert_eq!(first.value, 17);
+        assert_eq!(last.value, 17);
+        assert!(m.first_and_last("nothing").is_none());
+    }
+}
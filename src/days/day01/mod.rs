@@ -1,9 +1,53 @@
 #![allow(dead_code)]
 
+pub mod matcher;
+pub mod report;
+pub mod scanner;
+
 mod puzzle_part_1;
 mod puzzle_part_2;
 
+use std::io::Error;
+
+use report::{diff, Report};
+
+use super::solver::Solver;
+
 pub fn main() {
-    let _ = puzzle_part_1::main();
-    let _ = puzzle_part_2::main();
+    let _ = puzzle_part_1::main(false);
+    let _ = puzzle_part_2::main(false, false);
+}
+
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let lines = input.lines().map(|s| s.to_owned()).collect();
+        Ok(Self { lines })
+    }
+
+    fn part1(&self) -> Option<String> {
+        Report::new(&self.lines, false)
+            .checked_total()
+            .map(|n| n.to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        Report::new(&self.lines, true)
+            .checked_total()
+            .map(|n| n.to_string())
+    }
+
+    fn trace(&self, part: u32) -> Option<String> {
+        Some(Report::new(&self.lines, part == 2).to_string())
+    }
+
+    fn diff(&self) -> Option<String> {
+        let part1 = Report::new(&self.lines, false);
+        let part2 = Report::new(&self.lines, true);
+        let changes: Vec<_> = diff(&part1, &part2).iter().map(|c| c.to_string()).collect();
+        Some(changes.join("\n"))
+    }
 }
@@ -49,36 +49,34 @@
 /// To begin, get your puzzle input.
 ///
 use std::fs::File;
-use std::io::{BufRead, BufReader, Error};
+use std::io::{BufRead, BufReader, Error, ErrorKind};
 
-const INPUT: &str = "src/days/day_1/puzzle_input";
+use super::report::Report;
 
-pub fn main() -> Result<(), Error> {
+const INPUT: &str = "puzzle_inputs/day01/puzzle.input";
+
+/// With `trace`, prints how the value of every line was recovered.
+pub fn main(trace: bool) -> Result<(), Error> {
     let input = File::open(INPUT)?;
     let buffered = BufReader::new(input);
 
-    let f = |line: Result<String, Error>| line.map(|s| recover_value(&s));
+    let mut lines = Vec::new();
+    for line in buffered.lines() {
+        lines.push(line?);
+    }
+
+    let report = Report::new(&lines, false);
+    if trace {
+        println!("{report}");
+    }
 
-    let sum: u32 = buffered
-        .lines()
-        .map(f)
-        .collect::<Result<Vec<_>, _>>()?
-        .iter()
-        .sum();
+    if let Some(line) = report.failures().next() {
+        let msg = format!("line {}: no digits", line.line);
+        return Err(Error::new(ErrorKind::InvalidData, msg));
+    }
 
+    let sum = report.total();
     assert_eq!(sum, 54927);
 
     Ok(())
 }
-
-#[rustfmt::skip]
-fn recover_value(s: &str) -> u32 {
-    let s = String::from(s);
-    let v = s.chars()
-    .filter(|c| c.is_numeric())
-    .map(|c| c.to_digit(10).unwrap())
-    .collect::<Vec<_>>();
-    
-    let n = v.len();
-    v[0] * 10 + v[n - 1]
-}
@@ -23,59 +23,42 @@
 /// What is the sum of all of the calibration values?
 ///
 use std::fs::File;
-use std::io::{BufRead, BufReader, Error};
+use std::io::{BufRead, BufReader, Error, ErrorKind};
 
-const INPUT: &str = "src/days/day_1/puzzle_input";
+use super::report::{diff, Report};
 
-pub fn main() -> Result<(), Error> {
+const INPUT: &str = "puzzle_inputs/day01/puzzle.input";
+
+/// With `trace`, prints how the value of every line was recovered, and with
+/// `diff` the lines whose value differs from part one.
+pub fn main(trace: bool, diff_part1: bool) -> Result<(), Error> {
     let input = File::open(INPUT)?;
     let buffered = BufReader::new(input);
 
-    let trans = |s: String| recover_value(&word2num(&s));
-    let f = |line: Result<String, Error>| line.map(trans);
-
-    let sum: u32 = buffered
-        .lines()
-        .map(f)
-        .collect::<Result<Vec<_>, _>>()?
-        .iter()
-        .sum();
-
-    assert_eq!(sum, 54581);
-    Ok(())
-}
+    let mut lines = Vec::new();
+    for line in buffered.lines() {
+        lines.push(line?);
+    }
 
-fn word2num(txt: &str) -> String {
-    let replacement = [
-        ("zero", "0o"),
-        ("one", "o1e"),
-        ("two", "t2o"),
-        ("three", "t3e"),
-        ("four", "4"),
-        ("five", "5e"),
-        ("six", "6"),
-        ("seven", "7n"),
-        ("eight", "e8t"),
-        ("nine", "n9e"),
-    ];
+    let report = Report::new(&lines, true);
+    if trace {
+        println!("{report}");
+    }
 
-    let mut line = String::from(txt);
+    if diff_part1 {
+        let part1 = Report::new(&lines, false);
+        for change in diff(&part1, &report) {
+            println!("{change}");
+        }
+    }
 
-    for (word, rep) in replacement.iter() {
-        line = line.replace(word, rep);
+    if let Some(line) = report.failures().next() {
+        let msg = format!("line {}: no digits", line.line);
+        return Err(Error::new(ErrorKind::InvalidData, msg));
     }
 
-    line.to_owned()
-}
+    let sum = report.total();
+    assert_eq!(sum, 54581);
 
-#[rustfmt::skip]
-fn recover_value(s: &str) -> u32 {
-    let s = String::from(s);
-    let v = s.chars()
-    .filter(|c| c.is_numeric())
-    .map(|c| c.to_digit(10).unwrap())
-    .collect::<Vec<_>>();
-    
-    let n = v.len();
-    v[0] * 10 + v[n - 1]
+    Ok(())
 }
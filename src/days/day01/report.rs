@@ -0,0 +1,182 @@
+use std::fmt;
+
+use super::matcher::Match;
+use super::scanner::matcher;
+
+/// How the calibration value of one line was recovered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineTrace {
+    /// One based line number.
+    pub line: usize,
+    pub text: String,
+    /// Every digit or word found, with its text, in order of position.
+    pub found: Vec<(String, Match)>,
+    pub first: Option<Match>,
+    pub last: Option<Match>,
+    /// `None` when the line has no digits.
+    pub value: Option<u32>,
+}
+
+impl LineTrace {
+    pub fn new(line: usize, text: &str, words: bool) -> Self {
+        let matcher = matcher(words);
+
+        let mut found: Vec<(String, Match)> = matcher
+            .find_all(text)
+            .into_iter()
+            .map(|m| (text[m.start..m.end].to_owned(), m))
+            .collect();
+        found.sort_by_key(|(_, m)| (m.start, m.end));
+
+        let (first, last) = match matcher.first_and_last(text) {
+            Some((f, l)) => (Some(f), Some(l)),
+            None => (None, None),
+        };
+
+        Self {
+            line,
+            text: text.to_owned(),
+            found,
+            first,
+            last,
+            value: first.zip(last).map(|(f, l)| f.value * 10 + l.value),
+        }
+    }
+}
+
+impl fmt::Display for LineTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found: Vec<String> = self
+            .found
+            .iter()
+            .map(|(s, m)| format!("{s}@{}", m.start))
+            .collect();
+        let digit = |m: Option<Match>| m.map_or("-".to_owned(), |m| m.value.to_string());
+        let value = self.value.map_or("none".to_owned(), |v| v.to_string());
+
+        write!(
+            f,
+            "{:>5}: {} [{}] first={} last={} value={}",
+            self.line,
+            self.text,
+            found.join(" "),
+            digit(self.first),
+            digit(self.last),
+            value
+        )
+    }
+}
+
+/// Per-line traces of one part of the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub lines: Vec<LineTrace>,
+}
+
+impl Report {
+    /// Traces every line; with `words` the spelled-out digits of part two
+    /// count as well.
+    pub fn new<S: AsRef<str>>(lines: &[S], words: bool) -> Self {
+        let lines = lines
+            .iter()
+            .enumerate()
+            .map(|(i, s)| LineTrace::new(i + 1, s.as_ref(), words))
+            .collect();
+        Self { lines }
+    }
+
+    /// Sum of the values of the lines that have one.
+    pub fn total(&self) -> u32 {
+        self.lines.iter().filter_map(|l| l.value).sum()
+    }
+
+    /// Sum of the values, `None` if some line has no digits.
+    pub fn checked_total(&self) -> Option<u32> {
+        self.failures().next().is_none().then(|| self.total())
+    }
+
+    /// Lines without any digit.
+    pub fn failures(&self) -> impl Iterator<Item = &LineTrace> + '_ {
+        self.lines.iter().filter(|l| l.value.is_none())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.lines.iter() {
+            writeln!(f, "{line}")?;
+        }
+        write!(f, "total: {}", self.total())
+    }
+}
+
+/// A line whose value differs between part one and part two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<'a> {
+    pub part1: &'a LineTrace,
+    pub part2: &'a LineTrace,
+}
+
+impl fmt::Display for Change<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |v: Option<u32>| v.map_or("none".to_owned(), |v| v.to_string());
+        writeln!(
+            f,
+            "{:>5}: {} {} -> {}",
+            self.part1.line,
+            self.part1.text,
+            value(self.part1.value),
+            value(self.part2.value)
+        )?;
+        writeln!(f, "  - {}", self.part1)?;
+        write!(f, "  + {}", self.part2)
+    }
+}
+
+/// The lines whose value changed between the part one and part two
+/// reports of the same input.
+pub fn diff<'a>(part1: &'a Report, part2: &'a Report) -> Vec<Change<'a>> {
+    part1
+        .lines
+        .iter()
+        .zip(part2.lines.iter())
+        .filter(|(a, b)| a.value != b.value)
+        .map(|(a, b)| Change { part1: a, part2: b })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        let t = LineTrace::new(1, "xtwone3four", true);
+        let found: Vec<&str> = t.found.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(found, vec!["two", "one", "3", "four"]);
+        assert_eq!(t.value, Some(24));
+        assert_eq!(
+            t.to_string(),
+            "    1: xtwone3four [two@1 one@3 3@6 four@7] first=2 last=4 value=24"
+        );
+
+        let t = LineTrace::new(2, "abc", false);
+        assert_eq!(t.value, None);
+        assert!(t.to_string().ends_with("first=- last=- value=none"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let lines = ["1abc2", "two1nine", "treb7uchet", "eightwo"];
+        let part1 = Report::new(&lines, false);
+        let part2 = Report::new(&lines, true);
+
+        assert_eq!(part1.total(), 12 + 11 + 77);
+        assert_eq!(part1.failures().count(), 1);
+        assert_eq!(part1.checked_total(), None);
+        assert_eq!(part2.checked_total(), Some(12 + 29 + 77 + 82));
+
+        let changed: Vec<usize> = diff(&part1, &part2).iter().map(|c| c.part1.line).collect();
+        assert_eq!(changed, vec![2, 4]);
+    }
+}
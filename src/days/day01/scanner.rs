@@ -0,0 +1,84 @@
+use std::sync::OnceLock;
+
+use super::matcher::Matcher;
+
+/// Spelled-out digits that count in part two, indexed by value - 1.
+pub const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The automaton for part one (numerals only) or part two (numerals and
+/// words), built on first use.
+pub fn matcher(words: bool) -> &'static Matcher {
+    static NUMERALS: OnceLock<Matcher> = OnceLock::new();
+    static ENGLISH: OnceLock<Matcher> = OnceLock::new();
+
+    if words {
+        ENGLISH.get_or_init(Matcher::english)
+    } else {
+        NUMERALS.get_or_init(Matcher::numerals)
+    }
+}
+
+/// The first digit of `s`, scanning from the start.
+pub fn first_digit(s: &str, words: bool) -> Option<u32> {
+    matcher(words)
+        .first_and_last(s)
+        .map(|(first, _)| first.value)
+}
+
+/// The last digit of `s`, scanning from the end. Matches may overlap the
+/// first one, so the last digit of "twone" is 1.
+pub fn last_digit(s: &str, words: bool) -> Option<u32> {
+    matcher(words).first_and_last(s).map(|(_, last)| last.value)
+}
+
+/// First and last digit of the line as a two-digit number, or `None` for a
+/// line without digits.
+pub fn calibration_value(s: &str, words: bool) -> Option<u32> {
+    let (first, last) = matcher(words).first_and_last(s)?;
+    Some(first.value * 10 + last.value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1_sample() {
+        let lines = ["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"];
+        let values: Option<Vec<u32>> = lines.iter().map(|l| calibration_value(l, false)).collect();
+        assert_eq!(values, Some(vec![12, 38, 15, 77]));
+    }
+
+    #[test]
+    fn test_part2_sample() {
+        let lines = [
+            "two1nine",
+            "eightwothree",
+            "abcone2threexyz",
+            "xtwone3four",
+            "4nineeightseven2",
+            "zoneight234",
+            "7pqrstsixteen",
+        ];
+        let values: Option<Vec<u32>> = lines.iter().map(|l| calibration_value(l, true)).collect();
+        assert_eq!(values, Some(vec![29, 83, 13, 24, 42, 14, 76]));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert_eq!(calibration_value("twone", true), Some(21));
+        assert_eq!(calibration_value("oneight", true), Some(18));
+        assert_eq!(calibration_value("sevenine", true), Some(79));
+        assert_eq!(calibration_value("eighthree", true), Some(83));
+        assert_eq!(calibration_value("nine", true), Some(99));
+    }
+
+    #[test]
+    fn test_no_digits() {
+        assert_eq!(calibration_value("abcdef", false), None);
+        assert_eq!(calibration_value("twone", false), None);
+        assert_eq!(calibration_value("", true), None);
+    }
+}
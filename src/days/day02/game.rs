@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+
+type Cubes = BTreeMap<String, u32>;
+
+/// One handful of cubes shown by the Elf, counted by color.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub cubes: Cubes,
+}
+
+impl Draw {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).cloned().unwrap_or(0)
+    }
+}
+
+/// A game record: `Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    /// Parses `Game N: a color, b color; c color, ...`, reporting the
+    /// column of the first malformed part.
+    pub fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(input);
+
+        cursor.skip_spaces();
+        let start = cursor.pos;
+        if cursor.take_while(|c| c.is_ascii_alphabetic()) != "Game" {
+            return Err(cursor.error_at(start, ParseErrorKind::ExpectedGame));
+        }
+
+        cursor.skip_spaces();
+        let start = cursor.pos;
+        let id = cursor.take_while(|c| c.is_ascii_alphanumeric());
+        let id = id
+            .parse()
+            .map_err(|_| cursor.error_at(start, ParseErrorKind::InvalidId(id)))?;
+
+        cursor.skip_spaces();
+        if !cursor.eat(':') {
+            return Err(cursor.error(ParseErrorKind::MissingColon));
+        }
+
+        let mut draws = Vec::new();
+        loop {
+            draws.push(parse_draw(&mut cursor)?);
+
+            if cursor.eat(';') {
+                continue;
+            }
+            if cursor.at_end() {
+                break;
+            }
+            return Err(cursor.error(ParseErrorKind::MissingSeparator));
+        }
+
+        Ok(Self { id, draws })
+    }
+
+    /// Like `from_str`, but panics on malformed input.
+    pub fn game_from_str(input: &str) -> Self {
+        match Self::from_str(input) {
+            Ok(game) => game,
+            Err(e) => panic!("{e}: {input}"),
+        }
+    }
+
+    /// Whether every draw of the game could have come out of `bag`.
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        bag.is_possible(self)
+    }
+
+    /// The fewest cubes of each color that make the game possible.
+    pub fn minimum_bag(&self) -> Bag {
+        let mut bag = Bag::default();
+
+        for draw in self.draws.iter() {
+            for (color, n) in draw.cubes.iter() {
+                let m = bag.cubes.entry(color.clone()).or_insert(0);
+                if *m < *n {
+                    *m = *n;
+                }
+            }
+        }
+
+        bag
+    }
+
+    /// Power of the minimum bag.
+    pub fn power(&self) -> u32 {
+        self.minimum_bag().power()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Bag {
+    pub cubes: Cubes,
+}
+
+impl Bag {
+    pub fn new(counts: &[(&str, u32)]) -> Self {
+        let cubes = counts.iter().map(|(c, n)| (c.to_string(), *n)).collect();
+        Self { cubes }
+    }
+
+    pub fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).cloned().unwrap_or(0)
+    }
+
+    /// Product of the counts of every color in the bag.
+    pub fn power(&self) -> u32 {
+        self.cubes.values().product()
+    }
+
+    pub fn is_possible(&self, game: &Game) -> bool {
+        for draw in &game.draws {
+            if draw.cubes.iter().any(|(c, n)| self.count(c) < *n) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// `count color (, count color)*`, followed by spaces.
+fn parse_draw(cursor: &mut Cursor) -> Result<Draw, ParseError> {
+    let mut draw = Draw::new();
+
+    loop {
+        cursor.skip_spaces();
+        let start = cursor.pos;
+        let count = cursor.take_while(|c| c.is_ascii_alphanumeric());
+        if count.is_empty() {
+            return Err(cursor.error(ParseErrorKind::EmptyDraw));
+        }
+        let n = count
+            .parse()
+            .map_err(|_| cursor.error_at(start, ParseErrorKind::InvalidCount(count)))?;
+
+        cursor.skip_spaces();
+        let start = cursor.pos;
+        let color = cursor.take_while(|c| c.is_ascii_alphabetic());
+        if color.is_empty() {
+            return Err(cursor.error(ParseErrorKind::MissingColor));
+        }
+        if draw.cubes.contains_key(&color) {
+            return Err(cursor.error_at(start, ParseErrorKind::DuplicateColor(color)));
+        }
+        draw.cubes.insert(color, n);
+
+        cursor.skip_spaces();
+        if !cursor.eat(',') {
+            return Ok(draw);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The line does not start with `Game`.
+    ExpectedGame,
+    InvalidId(String),
+    /// No ':' after the game id.
+    MissingColon,
+    /// A cube count that is not a number.
+    InvalidCount(String),
+    /// A count without a color after it.
+    MissingColor,
+    /// The same color twice in one draw.
+    DuplicateColor(String),
+    /// A cube not preceded by ',' or a draw not preceded by ';'.
+    MissingSeparator,
+    /// Nothing between two separators, or after the last one.
+    EmptyDraw,
+}
+
+/// A parse failure at a one based character column of the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExpectedGame => write!(f, "expected 'Game'"),
+            Self::InvalidId(s) => write!(f, "invalid game id {s:?}"),
+            Self::MissingColon => write!(f, "expected ':' after game id"),
+            Self::InvalidCount(s) => write!(f, "invalid cube count {s:?}"),
+            Self::MissingColor => write!(f, "expected a color"),
+            Self::DuplicateColor(s) => write!(f, "color {s:?} appears twice in one draw"),
+            Self::MissingSeparator => write!(f, "expected ',' or ';'"),
+            Self::EmptyDraw => write!(f, "expected a cube count"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.kind)
+    }
+}
+
+impl error::Error for ParseError {}
+
+struct Cursor {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Cursor {
+    fn new(input: &str) -> Self {
+        Self {
+            chars: input.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ');
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.chars.get(self.pos) == Some(&c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.pos < self.chars.len() && f(self.chars[self.pos]) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.pos, kind)
+    }
+
+    fn error_at(&self, pos: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            column: pos + 1,
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: [&str; 5] = [
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    ];
+
+    fn games() -> Vec<Game> {
+        SAMPLE.iter().map(|s| Game::game_from_str(s)).collect()
+    }
+
+    #[test]
+    fn test_sample_part1() {
+        let bag = Bag::new(&[("red", 12), ("green", 13), ("blue", 14)]);
+        let id_sum: u32 = games()
+            .iter()
+            .filter(|g| g.is_possible(&bag))
+            .map(|g| g.id)
+            .sum();
+        assert_eq!(id_sum, 8);
+    }
+
+    #[test]
+    fn test_sample_part2() {
+        let games = games();
+        let bag = games[0].minimum_bag();
+        assert_eq!(bag, Bag::new(&[("red", 4), ("green", 2), ("blue", 6)]));
+
+        let powers: Vec<u32> = games.iter().map(|g| g.power()).collect();
+        assert_eq!(powers, vec![48, 12, 1560, 630, 36]);
+        assert_eq!(powers.iter().sum::<u32>(), 2286);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseErrorKind::*;
+
+        let err = |s| {
+            let e = Game::from_str(s).unwrap_err();
+            (e.column, e.kind)
+        };
+
+        assert_eq!(err("Gam 1: 3 red"), (1, ExpectedGame));
+        assert_eq!(err("Game x: 3 red"), (6, InvalidId("x".to_owned())));
+        assert_eq!(err("Game 1 3 red"), (8, MissingColon));
+        assert_eq!(
+            err("Game 1: 3 red, x blue"),
+            (16, InvalidCount("x".to_owned()))
+        );
+        assert_eq!(err("Game 1: 3 red; 4"), (17, MissingColor));
+        assert_eq!(
+            err("Game 1: 3 red, 4 blue, 5 red"),
+            (26, DuplicateColor("red".to_owned()))
+        );
+        assert_eq!(err("Game 1: 3 red 4 blue"), (15, MissingSeparator));
+        assert_eq!(err("Game 1: 3 red;; 4 blue"), (15, EmptyDraw));
+        assert_eq!(err("Game 1: 3 red;"), (15, EmptyDraw));
+
+        let e = Game::from_str("Game 1: 3 red, 3x blue").unwrap_err();
+        assert_eq!(e.to_string(), "column 16: invalid cube count \"3x\"");
+    }
+
+    #[test]
+    fn test_duplicate_color_across_draws() {
+        let game = Game::from_str("Game 2: 3 red; 5 red").unwrap();
+        assert_eq!(game.draws.len(), 2);
+        assert_eq!(game.minimum_bag().count("red"), 5);
+    }
+
+    #[test]
+    fn test_new_colors() {
+        let game = Game::game_from_str("Game 7: 3 yellow, 2 red; 1 purple, 4 red");
+
+        let bag = Bag::new(&[("red", 12), ("green", 13), ("blue", 14)]);
+        assert!(!game.is_possible(&bag));
+
+        let bag = game.minimum_bag();
+        assert_eq!(bag.count("red"), 4);
+        assert_eq!(bag.count("yellow"), 3);
+        assert!(game.is_possible(&bag));
+        assert_eq!(game.power(), 12);
+    }
+}
@@ -0,0 +1,149 @@
+//! Statistical questions about the bag behind a game, assuming every cube
+//! of a handful is drawn independently with replacement, so that a draw
+//! follows a multinomial distribution over the colors in the bag.
+
+use std::collections::BTreeMap;
+
+use super::game::{Bag, Game};
+
+/// How many cubes of each color were shown over the whole game.
+pub fn observed_totals(game: &Game) -> BTreeMap<String, u32> {
+    let mut totals = BTreeMap::new();
+    for draw in game.draws.iter() {
+        for (color, n) in draw.cubes.iter() {
+            *totals.entry(color.clone()).or_insert(0) += n;
+        }
+    }
+    totals
+}
+
+/// Maximum-likelihood share of each color in the bag: the fraction of all
+/// shown cubes that had that color.
+pub fn mle_proportions(game: &Game) -> BTreeMap<String, f64> {
+    let totals = observed_totals(game);
+    let all: u32 = totals.values().sum();
+
+    totals
+        .into_iter()
+        .filter(|(_, n)| *n > 0)
+        .map(|(c, n)| (c, n as f64 / all as f64))
+        .collect()
+}
+
+/// Maximum-likelihood bag holding exactly `size` cubes.
+///
+/// Every color that was shown gets at least one cube, the rest are handed
+/// out one at a time to the color whose likelihood gains most from it.
+/// Returns `None` if `size` is smaller than the number of colors shown.
+pub fn mle_bag(game: &Game, size: u32) -> Option<Bag> {
+    let totals: Vec<(String, u32)> = observed_totals(game)
+        .into_iter()
+        .filter(|(_, n)| *n > 0)
+        .collect();
+
+    if (size as usize) < totals.len() {
+        return None;
+    }
+
+    let mut counts = vec![1u32; totals.len()];
+    for _ in totals.len()..size as usize {
+        // adding a cube of color c changes the log-likelihood by
+        // k_c * ln((n_c + 1) / n_c), the common 1 / size factor aside
+        let gain = |i: usize| totals[i].1 as f64 * ((counts[i] + 1) as f64 / counts[i] as f64).ln();
+        let best = (0..counts.len()).max_by(|a, b| gain(*a).total_cmp(&gain(*b)));
+        match best {
+            Some(i) => counts[i] += 1,
+            None => break,
+        }
+    }
+
+    let cubes = totals
+        .into_iter()
+        .zip(counts)
+        .map(|((c, _), n)| (c, n))
+        .collect();
+    Some(Bag { cubes })
+}
+
+/// Natural log of the probability that `bag` produced every draw of the
+/// game, given the size of each handful. Negative infinity if some draw
+/// shows a color the bag does not hold.
+pub fn log_likelihood(game: &Game, bag: &Bag) -> f64 {
+    let size: u32 = bag.cubes.values().sum();
+    let mut total = 0.0;
+
+    for draw in game.draws.iter() {
+        let handful: u32 = draw.cubes.values().sum();
+        total += ln_factorial(handful);
+
+        for (color, k) in draw.cubes.iter() {
+            if *k == 0 {
+                continue;
+            }
+
+            let n = bag.count(color);
+            if n == 0 {
+                return f64::NEG_INFINITY;
+            }
+
+            let p = n as f64 / size as f64;
+            total += *k as f64 * p.ln() - ln_factorial(*k);
+        }
+    }
+
+    total
+}
+
+/// Probability that `bag` produced every draw of the game.
+pub fn likelihood(game: &Game, bag: &Bag) -> f64 {
+    log_likelihood(game, bag).exp()
+}
+
+fn ln_factorial(n: u32) -> f64 {
+    (2..=n).map(|i| (i as f64).ln()).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_likelihood() {
+        let bag = Bag::new(&[("red", 1), ("blue", 1)]);
+
+        let game = Game::game_from_str("Game 1: 2 red");
+        assert!(close(likelihood(&game, &bag), 0.25));
+
+        let game = Game::game_from_str("Game 1: 1 red, 1 blue");
+        assert!(close(likelihood(&game, &bag), 0.5));
+
+        let game = Game::game_from_str("Game 1: 1 red, 1 blue; 2 red");
+        assert!(close(likelihood(&game, &bag), 0.125));
+
+        let game = Game::game_from_str("Game 1: 1 green");
+        assert_eq!(likelihood(&game, &bag), 0.0);
+    }
+
+    #[test]
+    fn test_mle() {
+        let game = Game::game_from_str("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+
+        let p = mle_proportions(&game);
+        assert!(close(p["blue"], 0.5));
+        assert!(close(p["red"], 5.0 / 18.0));
+
+        let bag = mle_bag(&game, 18).unwrap();
+        assert_eq!(bag, Bag::new(&[("blue", 9), ("red", 5), ("green", 4)]));
+
+        let small = mle_bag(&game, 3).unwrap();
+        assert_eq!(small, Bag::new(&[("blue", 1), ("red", 1), ("green", 1)]));
+        assert!(mle_bag(&game, 2).is_none());
+
+        let elf = Bag::new(&[("red", 12), ("green", 13), ("blue", 14)]);
+        assert!(log_likelihood(&game, &bag) > log_likelihood(&game, &elf));
+    }
+}
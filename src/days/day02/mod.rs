@@ -1,9 +1,51 @@
 #![allow(dead_code)]
 
+pub mod game;
+pub mod inference;
+
 mod puzzle_part_1;
 mod puzzle_part_2;
 
+use std::io::{Error, ErrorKind};
+
+use game::{Bag, Game};
+
+use super::solver::Solver;
+
 pub fn main() {
     //puzzle_part_1::main();
     //let _ = puzzle_part_2::main();
 }
+
+pub struct Puzzle {
+    games: Vec<Game>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let mut games = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let g = Game::from_str(line).map_err(|e| {
+                Error::new(ErrorKind::InvalidData, format!("line {}: {}", i + 1, e))
+            })?;
+            games.push(g);
+        }
+        Ok(Self { games })
+    }
+
+    fn part1(&self) -> Option<String> {
+        let bag = Bag::new(&[("red", 12), ("green", 13), ("blue", 14)]);
+        let id_sum: u32 = self
+            .games
+            .iter()
+            .filter(|g| g.is_possible(&bag))
+            .map(|g| g.id)
+            .sum();
+        Some(id_sum.to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        let sum_of_powers: u32 = self.games.iter().map(|g| g.power()).sum();
+        Some(sum_of_powers.to_string())
+    }
+}
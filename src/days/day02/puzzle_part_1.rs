@@ -62,31 +62,30 @@
 ///
 ///
 use std::fs::File;
-use std::io::{BufRead, BufReader, Error};
+use std::io::{BufRead, BufReader, Error, ErrorKind};
 
-const INPUT: &str = "src/days/day_2/puzzle_input";
+use super::game::{Bag, Game};
+
+const INPUT: &str = "puzzle_inputs/day02/puzzle.input";
 
 pub fn main() -> Result<(), Error> {
     let input = File::open(INPUT)?;
     let buffered = BufReader::new(input);
 
     let mut games = Vec::new();
-    for line in buffered.lines() {
+    for (i, line) in buffered.lines().enumerate() {
         let s = line?;
-        let g = Game::game_from_str(&s);
+        let g = Game::from_str(&s)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("line {}: {}", i + 1, e)))?;
         games.push(g);
     }
 
-    let bag = Bag {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
+    let bag = Bag::new(&[("red", 12), ("green", 13), ("blue", 14)]);
 
     let id_sum: u32 = games
         .iter()
         .filter(|g| g.is_possible(&bag))
-        .map(|g| g.0)
+        .map(|g| g.id)
         .sum();
 
     // println!("{id_sum}");
@@ -94,96 +93,3 @@ pub fn main() -> Result<(), Error> {
 
     Ok(())
 }
-
-#[derive(Debug, Default)]
-struct Draw {
-    red: u32,
-    blue: u32,
-    green: u32,
-}
-
-impl Draw {
-    fn new() -> Self {
-        Self::default()
-    }
-}
-
-impl Draw {
-    /// parse input of the form: "10 red"
-    fn set_cube_from_str(&mut self, input: &str) {
-        let cube: Vec<&str> = input
-            .split(' ')
-            .filter(|s| !s.is_empty() && s.chars().all(char::is_alphanumeric))
-            .collect();
-
-        match cube.as_slice() {
-            [n, "red"] => self.red = n.parse().unwrap_or(self.red),
-            [n, "blue"] => self.blue = n.parse().unwrap_or(self.blue),
-            [n, "green"] => self.green = n.parse().unwrap_or(self.green),
-            _ => {
-                println!("Unknown pattern: {input}");
-            }
-        }
-    }
-}
-
-#[derive(Debug)]
-struct Game(u32, Vec<Draw>);
-
-impl Game {
-    fn game_from_str(input: &str) -> Self {
-        let tokens = tokenize(input, ':');
-        if tokens.len() != 2 {
-            panic!("Unknown pattern: {input}");
-        }
-
-        let id = tokenize(tokens[0], ' ');
-        if id.len() != 2 || !id[1].chars().all(|c| c.is_ascii_digit()) {
-            panic!("Unknown id part: {:?}", id);
-        }
-
-        let id = id[1].parse().unwrap_or_default();
-
-        let cube_lists = tokenize(tokens[1], ';');
-
-        let mut draws = Vec::new();
-        for cube_list in cube_lists {
-            let mut draw = Draw::new();
-
-            for cube in tokenize(cube_list, ',') {
-                draw.set_cube_from_str(cube);
-            }
-
-            draws.push(draw);
-        }
-
-        Self(id, draws)
-    }
-
-    fn is_possible(&self, bag: &Bag) -> bool {
-        bag.is_possible(self)
-    }
-}
-
-#[derive(Debug, Default)]
-struct Bag {
-    red: u32,
-    blue: u32,
-    green: u32,
-}
-
-impl Bag {
-    fn is_possible(&self, game: &Game) -> bool {
-        for draw in &game.1 {
-            if self.red < draw.red || self.blue < draw.blue || self.green < draw.green {
-                return false;
-            }
-        }
-
-        true
-    }
-}
-
-fn tokenize(input: &str, del: char) -> Vec<&str> {
-    input.split(del).filter(|tok| !tok.is_empty()).collect()
-}
@@ -1,10 +1,52 @@
 #![allow(dead_code)]
 
+pub mod parts;
+pub mod render;
+pub mod schematic;
+
 mod puzzle_part1;
+mod puzzle_part2;
+
+use std::io::{Error, ErrorKind};
+
+use parts::PartGraph;
+use schematic::Schematic;
 
-use std::io::Error;
+use super::solver::Solver;
 
 pub fn main() -> Result<(), Error> {
     puzzle_part1::main()?;
+    puzzle_part2::main()?;
     Ok(())
 }
+
+/// The example schematic from the puzzle text, shared by the tests.
+#[cfg(test)]
+fn sample() -> Schematic {
+    let input = std::fs::read_to_string("puzzle_inputs/day03/sample_input").unwrap();
+    let lines: Vec<&str> = input.lines().collect();
+    Schematic::from_lines(&lines).unwrap()
+}
+
+pub struct Puzzle {
+    graph: PartGraph,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let lines: Vec<&str> = input.lines().collect();
+        let schematic =
+            Schematic::from_lines(&lines).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        Ok(Self {
+            graph: PartGraph::new(&schematic),
+        })
+    }
+
+    fn part1(&self) -> Option<String> {
+        Some(self.graph.part_number_sum().to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        Some(self.graph.gear_ratio_sum().to_string())
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use super::schematic::{NumberSpan, Schematic, Symbol};
+use crate::days::grid::Point;
+
+/// Bipartite adjacency graph between the numbers and the symbols of a
+/// schematic. A number and a symbol are connected when the symbol lies on
+/// the border of the number's span, diagonals included.
+#[derive(Debug, Clone)]
+pub struct PartGraph {
+    numbers: Vec<NumberSpan>,
+    symbols: Vec<Symbol>,
+    number_edges: Vec<Vec<usize>>,
+    symbol_edges: Vec<Vec<usize>>,
+}
+
+impl PartGraph {
+    pub fn new(schematic: &Schematic) -> Self {
+        let numbers = schematic.numbers();
+        let symbols = schematic.symbols();
+
+        let symbol_at: HashMap<Point, usize> = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.pos, i))
+            .collect();
+
+        let mut number_edges = vec![Vec::new(); numbers.len()];
+        let mut symbol_edges = vec![Vec::new(); symbols.len()];
+
+        for (n, span) in numbers.iter().enumerate() {
+            for p in schematic.border(span.start, span.end) {
+                if let Some(&s) = symbol_at.get(&p) {
+                    number_edges[n].push(s);
+                    symbol_edges[s].push(n);
+                }
+            }
+        }
+
+        Self {
+            numbers,
+            symbols,
+            number_edges,
+            symbol_edges,
+        }
+    }
+
+    pub fn numbers(&self) -> &[NumberSpan] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Symbols adjacent to the `n`th number.
+    pub fn symbols_of(&self, n: usize) -> impl Iterator<Item = &Symbol> + '_ {
+        self.number_edges[n].iter().map(|s| &self.symbols[*s])
+    }
+
+    /// Numbers adjacent to the `s`th symbol.
+    pub fn numbers_of(&self, s: usize) -> impl Iterator<Item = &NumberSpan> + '_ {
+        self.symbol_edges[s].iter().map(|n| &self.numbers[*n])
+    }
+
+    /// Numbers adjacent to at least one symbol `ch`.
+    pub fn numbers_touching(&self, ch: char) -> Vec<&NumberSpan> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|(n, _)| self.symbols_of(*n).any(|s| s.ch == ch))
+            .map(|(_, span)| span)
+            .collect()
+    }
+
+    /// Indices of the symbols adjacent to exactly `k` numbers.
+    pub fn symbols_with_degree(&self, k: usize) -> Vec<usize> {
+        (0..self.symbols.len())
+            .filter(|s| self.symbol_edges[*s].len() == k)
+            .collect()
+    }
+
+    /// Numbers adjacent to any symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberSpan> + '_ {
+        self.numbers
+            .iter()
+            .zip(self.number_edges.iter())
+            .filter(|(_, edges)| !edges.is_empty())
+            .map(|(span, _)| span)
+    }
+
+    pub fn part_number_sum(&self) -> u32 {
+        self.part_numbers().map(|n| n.value).sum()
+    }
+
+    /// Sum over every '*' adjacent to exactly two numbers of the product of
+    /// those numbers.
+    pub fn gear_ratio_sum(&self) -> u32 {
+        self.symbols_with_degree(2)
+            .into_iter()
+            .filter(|s| self.symbols[*s].ch == '*')
+            .map(|s| self.numbers_of(s).map(|n| n.value).product::<u32>())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day03::sample;
+
+    fn graph() -> PartGraph {
+        PartGraph::new(&sample())
+    }
+
+    #[test]
+    fn test_numbers_and_symbols() {
+        let g = graph();
+        assert_eq!(g.numbers().len(), 10);
+        assert_eq!(g.symbols().len(), 6);
+        assert_eq!(
+            g.numbers()[1],
+            NumberSpan {
+                value: 114,
+                start: Point::new(5, 0),
+                end: Point::new(7, 0)
+            }
+        );
+    }
+
+    #[test]
+    fn test_queries() {
+        let g = graph();
+
+        let hash: Vec<u32> = g.numbers_touching('#').iter().map(|n| n.value).collect();
+        assert_eq!(hash, vec![633]);
+
+        let lonely: Vec<char> = g
+            .symbols_with_degree(1)
+            .iter()
+            .map(|s| g.symbols()[*s].ch)
+            .collect();
+        assert_eq!(lonely, vec!['#', '*', '+', '$']);
+    }
+
+    #[test]
+    fn test_sample_answers() {
+        let g = graph();
+        assert_eq!(g.part_number_sum(), 4361);
+        assert_eq!(g.gear_ratio_sum(), 467835);
+    }
+}
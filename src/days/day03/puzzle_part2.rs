@@ -0,0 +1,89 @@
+/// --- Part Two ---
+///
+/// The engineer finds the missing part and installs it in the engine! As
+/// the engine springs to life, you jump in the closest gondola, finally
+/// ready to ascend to the water source.
+///
+/// You don't seem to be going very fast, though. Maybe something is still
+/// wrong? Fortunately, the gondola has a phone labeled "help", so you pick
+/// it up and the engineer answers.
+///
+/// Before you can explain the situation, she suggests that you look out
+/// the window. There stands the engineer, holding a phone in one hand and
+/// waving with the other. You're going so slowly that you haven't even left
+/// the station. You exit the gondola.
+///
+/// The missing part wasn't the only issue - one of the gears in the engine
+/// is wrong. A gear is any * symbol that is adjacent to exactly two part
+/// numbers. Its gear ratio is the result of multiplying those two numbers
+/// together.
+///
+/// This time, you need to find the gear ratio of every gear and add them
+/// all up so that the engineer can figure out which gear needs to be
+/// replaced.
+///
+/// Consider the same engine schematic again:
+///
+/// 467..114..
+/// ...*......
+/// ..35..633.
+/// ......#...
+/// 617*......
+/// .....+.58.
+/// ..592.....
+/// ......755.
+/// ...$.*....
+/// .664.598..
+///
+/// In this schematic, there are two gears. The first is in the top left; it
+/// has part numbers 467 and 35, so its gear ratio is 16345. The second gear
+/// is in the lower right; its gear ratio is 451490. (The * adjacent to 617
+/// is not a gear because it is only adjacent to one part number.) Adding up
+/// all of the gear ratios produces 467835.
+///
+/// What is the sum of all of the gear ratios in your engine schematic?
+///
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind};
+
+use super::parts::PartGraph;
+use super::schematic::{Schematic, SchematicError};
+
+//const INPUT: &str = "puzzle_inputs/day03/sample_input";
+const INPUT: &str = "puzzle_inputs/day03/puzzle_input";
+
+pub fn main() -> Result<(), Error> {
+    let input = File::open(INPUT)?;
+    let buffered = BufReader::new(input);
+
+    let mut lines = Vec::new();
+    for line in buffered.lines() {
+        lines.push(line?);
+    }
+
+    let sum_gear_ratios =
+        sum_gear_ratios(&lines).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    println!("{:?}", sum_gear_ratios);
+    assert_eq!(sum_gear_ratios, 84051670);
+
+    Ok(())
+}
+
+pub fn sum_gear_ratios<S: AsRef<str>>(lines: &[S]) -> Result<u32, SchematicError> {
+    let schematic = Schematic::from_lines(lines)?;
+    Ok(PartGraph::new(&schematic).gear_ratio_sum())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_sample_input() {
+        let input = fs::read_to_string("puzzle_inputs/day03/sample_input").unwrap();
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(sum_gear_ratios(&lines), Ok(467835));
+    }
+}
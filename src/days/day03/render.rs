@@ -0,0 +1,233 @@
+use std::collections::{HashMap, HashSet};
+
+use super::parts::PartGraph;
+use super::schematic::Schematic;
+use crate::days::grid::Point;
+
+/// How a cell of the schematic is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Plain,
+    PartNumber,
+    NonPartNumber,
+    Symbol,
+    Gear,
+}
+
+impl Highlight {
+    fn ansi(self) -> Option<&'static str> {
+        match self {
+            Highlight::Plain => None,
+            Highlight::PartNumber => Some("\x1b[32m"),
+            Highlight::NonPartNumber => Some("\x1b[31m"),
+            Highlight::Symbol => Some("\x1b[33m"),
+            Highlight::Gear => Some("\x1b[1;35m"),
+        }
+    }
+
+    fn css_class(self) -> Option<&'static str> {
+        match self {
+            Highlight::Plain => None,
+            Highlight::PartNumber => Some("part"),
+            Highlight::NonPartNumber => Some("non-part"),
+            Highlight::Symbol => Some("symbol"),
+            Highlight::Gear => Some("gear"),
+        }
+    }
+}
+
+/// Rectangle of the schematic to render, both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub top_left: Point,
+    pub bottom_right: Point,
+}
+
+impl Window {
+    /// The window of cells within `radius` of `center`, clipped to the
+    /// schematic.
+    pub fn around(schematic: &Schematic, center: Point, radius: usize) -> Self {
+        let radius = radius as i64;
+        let (right, bottom) = (schematic.cols() as i64 - 1, schematic.rows() as i64 - 1);
+        Self {
+            top_left: Point::new((center.x - radius).max(0), (center.y - radius).max(0)),
+            bottom_right: Point::new(
+                (center.x + radius).min(right),
+                (center.y + radius).min(bottom),
+            ),
+        }
+    }
+
+    fn whole(schematic: &Schematic) -> Self {
+        Self {
+            top_left: Point::new(0, 0),
+            bottom_right: Point::new(schematic.cols() as i64 - 1, schematic.rows() as i64 - 1),
+        }
+    }
+}
+
+/// Renders a schematic with its part numbers, non-part numbers, symbols and
+/// gears highlighted.
+pub struct Renderer<'a> {
+    schematic: &'a Schematic,
+    highlights: HashMap<Point, Highlight>,
+    window: Window,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(schematic: &'a Schematic) -> Self {
+        let graph = PartGraph::new(schematic);
+        let mut highlights = HashMap::new();
+
+        for (n, span) in graph.numbers().iter().enumerate() {
+            let h = if graph.symbols_of(n).next().is_some() {
+                Highlight::PartNumber
+            } else {
+                Highlight::NonPartNumber
+            };
+
+            for x in span.start.x..=span.end.x {
+                highlights.insert(Point::new(x, span.start.y), h);
+            }
+        }
+
+        let gears: HashSet<usize> = graph.symbols_with_degree(2).into_iter().collect();
+        for (s, symbol) in graph.symbols().iter().enumerate() {
+            let h = if symbol.ch == '*' && gears.contains(&s) {
+                Highlight::Gear
+            } else {
+                Highlight::Symbol
+            };
+            highlights.insert(symbol.pos, h);
+        }
+
+        Self {
+            schematic,
+            highlights,
+            window: Window::whole(schematic),
+        }
+    }
+
+    /// Only render the cells inside `window`.
+    pub fn crop(mut self, window: Window) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn highlight(&self, p: Point) -> Highlight {
+        self.highlights.get(&p).cloned().unwrap_or(Highlight::Plain)
+    }
+
+    /// The window as text with ANSI color escapes, one line per row.
+    pub fn to_ansi(&self) -> String {
+        const RESET: &str = "\x1b[0m";
+
+        let mut out = String::new();
+        for row in self.rows() {
+            for (p, ch) in row {
+                match self.highlight(p).ansi() {
+                    Some(color) => {
+                        out.push_str(color);
+                        out.push(ch);
+                        out.push_str(RESET);
+                    }
+                    None => out.push(ch),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The window as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<style>\n\
+             pre { font-family: monospace; }\n\
+             .part { color: green; }\n\
+             .non-part { color: red; }\n\
+             .symbol { color: orange; }\n\
+             .gear { color: purple; font-weight: bold; }\n\
+             </style>\n</head>\n<body>\n<pre>\n",
+        );
+
+        for row in self.rows() {
+            for (p, ch) in row {
+                let ch = html_escape(ch);
+                match self.highlight(p).css_class() {
+                    Some(class) => out.push_str(&format!("<span class=\"{class}\">{ch}</span>")),
+                    None => out.push_str(&ch),
+                }
+            }
+            out.push('\n');
+        }
+
+        out.push_str("</pre>\n</body>\n</html>\n");
+        out
+    }
+
+    fn rows(&self) -> impl Iterator<Item = Vec<(Point, char)>> + '_ {
+        let Window {
+            top_left,
+            bottom_right,
+        } = self.window;
+
+        (top_left.y..=bottom_right.y).map(move |y| {
+            (top_left.x..=bottom_right.x)
+                .map(|x| Point::new(x, y))
+                .filter_map(|p| self.schematic.get(p).map(|ch| (p, ch)))
+                .collect()
+        })
+    }
+}
+
+fn html_escape(ch: char) -> String {
+    match ch {
+        '&' => "&amp;".to_owned(),
+        '<' => "&lt;".to_owned(),
+        '>' => "&gt;".to_owned(),
+        '"' => "&quot;".to_owned(),
+        _ => ch.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day03::sample;
+
+    #[test]
+    fn test_highlights() {
+        let schematic = sample();
+        let renderer = Renderer::new(&schematic);
+
+        assert_eq!(renderer.highlight(Point::new(0, 0)), Highlight::PartNumber);
+        assert_eq!(
+            renderer.highlight(Point::new(5, 0)),
+            Highlight::NonPartNumber
+        );
+        assert_eq!(renderer.highlight(Point::new(3, 1)), Highlight::Gear);
+        assert_eq!(renderer.highlight(Point::new(3, 4)), Highlight::Symbol);
+        assert_eq!(renderer.highlight(Point::new(3, 0)), Highlight::Plain);
+    }
+
+    #[test]
+    fn test_cropped_ansi() {
+        let schematic = sample();
+        let window = Window::around(&schematic, Point::new(0, 0), 1);
+        let text = Renderer::new(&schematic).crop(window).to_ansi();
+
+        let plain: String = text.replace("\x1b[0m", "").replace("\x1b[32m", "");
+        assert_eq!(plain, "46\n..\n");
+    }
+
+    #[test]
+    fn test_html() {
+        let schematic = sample();
+        let html = Renderer::new(&schematic).to_html();
+
+        assert!(html.contains("<span class=\"non-part\">1</span>"));
+        assert!(html.contains("<span class=\"gear\">*</span>"));
+        assert!(html.contains("<span class=\"symbol\">$</span>"));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::days::grid::{Grid, GridError, Point};
+
+/// The engine schematic as a grid of characters, sized from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    grid: Grid<char>,
+}
+
+/// A schematic fails to load exactly when its grid does.
+pub type SchematicError = GridError;
+
+impl Schematic {
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, SchematicError> {
+        Ok(Self {
+            grid: Grid::from_lines(lines)?,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.grid.height()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn get(&self, p: Point) -> Option<char> {
+        self.grid.get(p).copied()
+    }
+
+    /// All characters of row `r`.
+    pub fn row(&self, r: usize) -> &[char] {
+        self.grid.row(r)
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        self.grid.points()
+    }
+
+    /// The in-bounds positions surrounding the horizontal span from `start`
+    /// to `end` (inclusive, same row), diagonals included.
+    pub fn border(&self, start: Point, end: Point) -> impl Iterator<Item = Point> + '_ {
+        self.grid.border(start, end)
+    }
+
+    /// The in-bounds 8-neighbourhood of `p`.
+    pub fn neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.grid.neighbours8(p)
+    }
+
+    /// Every run of digits, row by row.
+    pub fn numbers(&self) -> Vec<NumberSpan> {
+        let mut spans = Vec::new();
+
+        for r in 0..self.rows() {
+            let row = self.row(r);
+
+            let mut i = 0;
+            loop {
+                while i < row.len() && !row[i].is_ascii_digit() {
+                    i += 1;
+                }
+
+                if i >= row.len() {
+                    break;
+                }
+                let start = i;
+
+                let mut value = 0;
+                while i < row.len() && row[i].is_ascii_digit() {
+                    value = value * 10 + row[i].to_digit(10).unwrap();
+                    i += 1;
+                }
+
+                let y = r as i64;
+                spans.push(NumberSpan {
+                    value,
+                    start: Point::new(start as i64, y),
+                    end: Point::new(i as i64 - 1, y),
+                });
+            }
+        }
+
+        spans
+    }
+
+    /// Every cell that is neither '.' nor a digit, row by row.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        self.positions()
+            .filter_map(|p| {
+                let ch = self.get(p)?;
+                is_symbol(ch).then_some(Symbol { ch, pos: p })
+            })
+            .collect()
+    }
+}
+
+pub fn is_symbol(ch: char) -> bool {
+    ch != '.' && !ch.is_ascii_digit()
+}
+
+/// A number in the schematic with the (inclusive) span of its digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberSpan {
+    pub value: u32,
+    pub start: Point,
+    pub end: Point,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub ch: char,
+    pub pos: Point,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ragged_rows() {
+        let err = Schematic::from_lines(&["467..", "...*", "..35."]).unwrap_err();
+        assert_eq!(
+            err,
+            SchematicError::RaggedRow {
+                line: 2,
+                expected: 5,
+                found: 4
+            }
+        );
+
+        let empty: [&str; 0] = [];
+        assert_eq!(Schematic::from_lines(&empty), Err(SchematicError::Empty));
+    }
+
+    #[test]
+    fn test_neighbors_are_bounds_checked() {
+        let s = Schematic::from_lines(&["123", "456", "789"]).unwrap();
+        assert_eq!(s.neighbors(Point::new(0, 0)).count(), 3);
+        assert_eq!(s.neighbors(Point::new(1, 1)).count(), 8);
+        assert_eq!(s.neighbors(Point::new(1, 2)).count(), 5);
+
+        let border: Vec<Point> = s.border(Point::new(0, 1), Point::new(2, 1)).collect();
+        assert_eq!(border.len(), 6);
+        assert!(border.iter().all(|p| p.y != 1));
+    }
+}
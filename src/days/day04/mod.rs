@@ -1,9 +1,39 @@
+pub mod scratchcard;
+
 mod puzzle_part1;
+mod puzzle_part2;
 
 use std::io::Error;
 
+use scratchcard::parse_cards;
+
+use super::solver::Solver;
+
 pub fn main() -> Result<(), Error> {
     puzzle_part1::main()?;
+    puzzle_part2::main()?;
 
     Ok(())
 }
+
+pub struct Puzzle {
+    matches: Vec<(u32, u32)>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let lines: Vec<&str> = input.lines().collect();
+        let cards = parse_cards(&lines)?;
+        Ok(Self {
+            matches: puzzle_part1::card_matches(&cards),
+        })
+    }
+
+    fn part1(&self) -> Option<String> {
+        Some(puzzle_part1::total_points(&self.matches).to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        Some(puzzle_part2::total_scratchcards(&self.matches).to_string())
+    }
+}
@@ -1,6 +1,7 @@
-use std::collections::HashSet;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Error};
+use std::io::{BufRead, BufReader, Error, ErrorKind};
+
+use super::scratchcard::{parse_cards, Scratchcard};
 
 //const INPUT: &str = "puzzle_inputs/day04/sample.input";
 const INPUT: &str = "puzzle_inputs/day04/puzzle.input";
@@ -15,19 +16,24 @@ pub fn main() -> Result<(), Error> {
         buffer.push(s);
     }
 
-    let puzzle_input = parse_lines(&buffer);
+    let puzzle_input = parse_cards(&buffer).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
 
-    let xs: Vec<(u32, u32)> = puzzle_input
-        .iter()
-        .map(|(id, w, c)| {
-            let s1: HashSet<u32> = HashSet::from_iter(w.iter().cloned());
-            let s2: HashSet<u32> = HashSet::from_iter(c.iter().cloned());
-            let s = s1.intersection(&s2);
-            (*id, s.count() as u32)
-        })
-        .collect();
+    let xs = card_matches(&puzzle_input);
+    let total_pts = total_points(&xs);
+
+    println!("{:?}", total_pts);
+    //assert_eq!(total_pts, 13);
+
+    Ok(())
+}
+
+/// `(id, number of winning numbers you have)` for every card.
+pub fn card_matches(cards: &[Scratchcard]) -> Vec<(u32, u32)> {
+    cards.iter().map(|c| (c.id, c.matches())).collect()
+}
 
-    let total_pts: u32 = xs
+pub fn total_points(matches: &[(u32, u32)]) -> u32 {
+    matches
         .iter()
         .map(|(_, pt)| {
             let base: u32 = 2;
@@ -37,35 +43,20 @@ pub fn main() -> Result<(), Error> {
                 0
             }
         })
-        .sum();
-
-    println!("{:?}", total_pts);
-    //assert_eq!(total_pts, 13);
-
-    Ok(())
+        .sum()
 }
 
-type T = (u32, Vec<u32>, Vec<u32>);
+#[cfg(test)]
+mod test {
+    use std::fs;
 
-fn parse_lines(lines: &[String]) -> Vec<T> {
-    let mut v = Vec::new();
+    use super::*;
 
-    for line in lines {
-        let xs: Vec<&str> = line.split(':').collect();
-        let card_id: Vec<_> = xs[0].split(' ').filter(|s| !s.is_empty()).collect();
-        let card_id: u32 = card_id[1].parse().unwrap_or_default();
-        let rest: Vec<_> = xs[1].split('|').filter(|s| !s.is_empty()).collect();
-        let winning_numbers: Vec<u32> = rest[0]
-            .split(' ')
-            .filter(|s| !s.is_empty())
-            .map(|s| s.parse().unwrap_or_default())
-            .collect();
-        let card_numbers: Vec<u32> = rest[1]
-            .split(' ')
-            .filter(|s| !s.is_empty())
-            .map(|s| s.parse().unwrap_or_default())
-            .collect();
-        v.push((card_id, winning_numbers, card_numbers));
+    #[test]
+    fn test_sample_input() {
+        let input = fs::read_to_string("puzzle_inputs/day04/sample.input").unwrap();
+        let lines: Vec<String> = input.lines().map(|s| s.to_owned()).collect();
+        let matches = card_matches(&parse_cards(&lines).unwrap());
+        assert_eq!(total_points(&matches), 13);
     }
-    v
 }
@@ -0,0 +1,80 @@
+// --- Part Two ---
+//
+// Just as you're about to report your findings to the Elf, one of you realizes that the rules have
+// actually been printed on the back of every card this whole time.
+//
+// There's no such thing as "points". Instead, scratchcards only cause you to win more scratchcards
+// equal to the number of winning numbers you have.
+//
+// Specifically, you win copies of the scratchcards below the winning card equal to the number of
+// matches. So, if card 10 were to have 5 matching numbers, you would win one copy each of cards
+// 11, 12, 13, 14, and 15.
+//
+// Copies of scratchcards are scored like normal scratchcards and have the same card number as the
+// card they copied. So, if you win a copy of card 10 and it has 5 matching numbers, it would then
+// win a copy of the same cards that the original card 10 won: cards 11, 12, 13, 14, and 15. This
+// process repeats until none of the copies cause you to win any more cards. (Cards will never make
+// you copy a card past the end of the table.)
+//
+// Process all of the original and copied scratchcards until no more scratchcards are won.
+// Including the original set of scratchcards, how many total scratchcards do you end up with?
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind};
+
+use super::puzzle_part1::card_matches;
+use super::scratchcard::parse_cards;
+
+//const INPUT: &str = "puzzle_inputs/day04/sample.input";
+const INPUT: &str = "puzzle_inputs/day04/puzzle.input";
+
+pub fn main() -> Result<(), Error> {
+    let input = File::open(INPUT)?;
+    let buffered = BufReader::new(input);
+
+    let mut buffer: Vec<String> = Vec::new();
+    for line in buffered.lines() {
+        let s = line?;
+        buffer.push(s);
+    }
+
+    let puzzle_input = parse_cards(&buffer).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+    let xs = card_matches(&puzzle_input);
+    let total_cards = total_scratchcards(&xs);
+
+    println!("{:?}", total_cards);
+    //assert_eq!(total_cards, 30);
+
+    Ok(())
+}
+
+/// Total number of cards held once every card, original or copy, has won
+/// copies of the cards below it. `matches` is ordered by card id.
+pub fn total_scratchcards(matches: &[(u32, u32)]) -> u32 {
+    let mut copies = vec![1; matches.len()];
+
+    for (i, (_, m)) in matches.iter().enumerate() {
+        let won = (i + 1)..(i + 1 + *m as usize).min(matches.len());
+        for j in won {
+            copies[j] += copies[i];
+        }
+    }
+
+    copies.iter().sum()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_sample_input() {
+        let input = fs::read_to_string("puzzle_inputs/day04/sample.input").unwrap();
+        let lines: Vec<String> = input.lines().map(|s| s.to_owned()).collect();
+        let matches = card_matches(&parse_cards(&lines).unwrap());
+        assert_eq!(total_scratchcards(&matches), 30);
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use crate::days::parse::{self, ParseError, ParseErrorKind};
+
+/// One line of the puzzle input: `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: u32,
+    winning: Vec<u32>,
+    numbers: Vec<u32>,
+    /// Both sides as bitsets, built once by `new`. `None` when a number is
+    /// too large for a `NumberSet`.
+    sets: Option<(NumberSet, NumberSet)>,
+}
+
+impl Scratchcard {
+    pub fn new(id: u32, winning: Vec<u32>, numbers: Vec<u32>) -> Self {
+        let sets = NumberSet::from_numbers(&winning).zip(NumberSet::from_numbers(&numbers));
+        Self {
+            id,
+            winning,
+            numbers,
+            sets,
+        }
+    }
+
+    /// Parses a single line; errors are on line 1.
+    pub fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (header, rest) = parse::key_value(s)?;
+
+        let id = match header.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["Card", n] => n.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            let kind = ParseErrorKind::Invalid {
+                what: "card header",
+                text: header.to_owned(),
+            };
+            ParseError::within(s, header, kind)
+        })?;
+
+        let (winning, numbers) = rest.split_once('|').ok_or_else(|| expected(s, "|"))?;
+
+        Ok(Self::new(
+            id,
+            parse_numbers(s, winning)?,
+            parse_numbers(s, numbers)?,
+        ))
+    }
+
+    /// How many of the numbers you have are winning numbers.
+    pub fn matches(&self) -> u32 {
+        match self.sets {
+            Some((w, n)) => w.intersection_count(n),
+            None => self.matches_hashed(),
+        }
+    }
+
+    /// `matches` for cards with numbers too large for a `NumberSet`.
+    fn matches_hashed(&self) -> u32 {
+        let winning: HashSet<&u32> = self.winning.iter().collect();
+        self.numbers.iter().filter(|n| winning.contains(n)).count() as u32
+    }
+}
+
+/// Set of numbers below 128 packed into the bits of a `u128`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumberSet(u128);
+
+impl NumberSet {
+    pub const CAPACITY: u32 = u128::BITS;
+
+    /// `None` if any number does not fit.
+    pub fn from_numbers(numbers: &[u32]) -> Option<Self> {
+        let mut bits = 0;
+        for n in numbers {
+            if *n >= Self::CAPACITY {
+                return None;
+            }
+            bits |= 1 << n;
+        }
+        Some(Self(bits))
+    }
+
+    pub fn contains(self, n: u32) -> bool {
+        n < Self::CAPACITY && self.0 & (1 << n) != 0
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn intersection_count(self, other: Self) -> u32 {
+        (self.0 & other.0).count_ones()
+    }
+}
+
+/// A `sep` missing from the end of `line`.
+fn expected(line: &str, sep: &str) -> ParseError {
+    let end = line.trim_end();
+    ParseError::within(
+        line,
+        &end[end.len()..],
+        ParseErrorKind::Expected(sep.to_owned()),
+    )
+}
+
+/// The numbers of `part`, one side of the card `line`.
+fn parse_numbers(line: &str, part: &str) -> Result<Vec<u32>, ParseError> {
+    let v: Vec<u32> = parse::numbers(part).map_err(|e| e.inside(line, part))?;
+
+    let mut seen = HashSet::new();
+    for (n, tok) in v.iter().zip(part.split_whitespace()) {
+        if !seen.insert(*n) {
+            let kind = ParseErrorKind::Duplicate(tok.to_owned());
+            return Err(ParseError::within(line, tok, kind));
+        }
+    }
+
+    Ok(v)
+}
+
+/// Parses every non-empty line as a card, checking that the ids run 1, 2, 3, ...
+pub fn parse_cards<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Scratchcard>, ParseError> {
+    let mut cards = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.as_ref();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let card = Scratchcard::from_str(line).map_err(|e| e.offset(i))?;
+        let expected = cards.len() as u32 + 1;
+        if card.id != expected {
+            let kind = ParseErrorKind::Expected(format!("Card {expected}"));
+            return Err(ParseError::within(line, line.trim_start(), kind).offset(i));
+        }
+
+        cards.push(card);
+    }
+
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let card = Scratchcard::from_str("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let card = card.unwrap();
+        assert_eq!(card.id, 1);
+        assert_eq!(card.winning, vec![41, 48, 83, 86, 17]);
+        assert_eq!(card.numbers.len(), 8);
+        assert_eq!(card.matches(), 4);
+    }
+
+    #[test]
+    fn test_number_set() {
+        let s = NumberSet::from_numbers(&[0, 5, 99, 127]).unwrap();
+        assert_eq!(s.len(), 4);
+        assert!(s.contains(127) && !s.contains(6) && !s.contains(500));
+        assert!(NumberSet::from_numbers(&[128]).is_none());
+
+        let t = NumberSet::from_numbers(&[5, 6, 127]).unwrap();
+        assert_eq!(s.intersection_count(t), 2);
+
+        let big = Scratchcard::new(1, vec![1, 200, 300], vec![300, 1, 2]);
+        assert_eq!(big.matches(), 2);
+    }
+
+    #[test]
+    fn test_errors() {
+        let parse = |s| Scratchcard::from_str(s).unwrap_err().to_string();
+        assert_eq!(parse("Card 1 41 | 41"), "line 1, column 15: expected \":\"");
+        assert_eq!(
+            parse("Crd 1: 41 | 41"),
+            "line 1, column 1: invalid card header \"Crd 1\""
+        );
+        assert_eq!(
+            parse("Card 1: 41 48 83"),
+            "line 1, column 17: expected \"|\""
+        );
+        assert_eq!(
+            parse("Card 1: 41 4x | 41"),
+            "line 1, column 12: invalid number \"4x\""
+        );
+        assert_eq!(
+            parse("Card 1: 41 | 41 7 41"),
+            "line 1, column 19: \"41\" appears twice"
+        );
+    }
+
+    #[test]
+    fn test_error_line() {
+        let lines = ["Card 1: 1 | 2", "Card 2: 3 | 4", "Card 4: 5 | 6"];
+        let err = parse_cards(&lines).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.to_string(), "line 3, column 1: expected \"Card 3\"");
+
+        let lines = ["Card 1: 1 | 2", "Card 2: 3 | 4 x"];
+        let err = parse_cards(&lines).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 15));
+    }
+
+    /// Compares bitset and hash set match counting on a million synthetic
+    /// cards. Run with
+    /// `cargo test --release bench_match_counting -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_match_counting() {
+        use std::time::Instant;
+
+        let mut seed: u64 = 0x2023_1204;
+        let mut next = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            ((seed >> 33) % 100) as u32
+        };
+        let mut side = |n| {
+            let mut v: Vec<u32> = Vec::new();
+            while v.len() < n {
+                let x = next();
+                if !v.contains(&x) {
+                    v.push(x);
+                }
+            }
+            v
+        };
+
+        let cards: Vec<Scratchcard> = (1..=1_000_000)
+            .map(|id| Scratchcard::new(id, side(10), side(25)))
+            .collect();
+
+        let now = Instant::now();
+        let hashed: u64 = cards.iter().map(|c| c.matches_hashed() as u64).sum();
+        let hashed_time = now.elapsed();
+
+        let now = Instant::now();
+        let bits: u64 = cards.iter().map(|c| c.matches() as u64).sum();
+        let bits_time = now.elapsed();
+
+        assert_eq!(hashed, bits);
+        println!("hash set: {hashed_time:?}, bitset: {bits_time:?}");
+    }
+}
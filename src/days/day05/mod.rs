@@ -2,8 +2,33 @@ mod puzzle_part1;
 
 use std::io::Error;
 
+use puzzle_part1::PuzzleInput;
+
+use super::solver::Solver;
+
 pub fn main() -> Result<(), Error> {
     puzzle_part1::main()?;
 
     Ok(())
 }
+
+pub struct Puzzle {
+    almanac: PuzzleInput,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            almanac: puzzle_part1::parse_lines(input)?,
+        })
+    }
+
+    fn part1(&self) -> Option<String> {
+        let (seeds, mappings) = &self.almanac;
+        puzzle_part1::lowest_location(seeds, mappings).map(|n| n.to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        None
+    }
+}
@@ -103,6 +103,9 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Error, Read};
 
+use crate::days::parse::{self, ParseError, ParseErrorKind, Section};
+use crate::days::ranges::RangeSet;
+
 //const INPUT: &str = "puzzle_inputs/day05/sample.input";
 const INPUT: &str = "puzzle_inputs/day05/puzzle.input";
 
@@ -111,93 +114,150 @@ pub fn main() -> Result<(), Error> {
     let mut buffer = String::new();
     input.read_to_string(&mut buffer)?;
 
-    let keys = [
-        "seed-to-soil",
-        "soil-to-fertilizer",
-        "fertilizer-to-water",
-        "water-to-light",
-        "light-to-temperature",
-        "temperature-to-humidity",
-        "humidity-to-location",
-    ];
+    let (seeds, mappings) = parse_lines(&buffer)?;
+
+    let min = lowest_location(&seeds, &mappings).unwrap();
+    println!("{:?}", min);
+
+    assert_eq!(min, 535088217);
+
+    Ok(())
+}
 
-    let (seeds, mappings) = parse_lines(&buffer);
+const KEYS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// Lowest location of any seed after applying every map in turn. `None` if
+/// there are no seeds or a map is missing.
+pub fn lowest_location(seeds: &[U], mappings: &HashMap<String, Vec<Vec<U>>>) -> Option<U> {
+    let mut mappers = Vec::new();
+    for k in KEYS {
+        mappers.push(get_mapper(mappings.get(k)?));
+    }
 
-    let f = move |x| {
+    let f = |x| {
         let mut v = x;
-        for k in keys {
-            let m = mappings.get(k);
-            let m = m.unwrap();
-            let mapper = get_mapper(m);
-            v = get_mapped_value(&mapper, v);
+        for mapper in mappers.iter() {
+            v = get_mapped_value(mapper, v);
         }
         v
     };
 
-    let min = seeds.iter().map(|s| f(*s)).min().unwrap();
-    println!("{:?}", min);
+    seeds.iter().map(|s| f(*s)).min()
+}
 
-    assert_ne!(min, 294198454);
+fn get_mapped_value(mapper: &Mapper, x: U) -> U {
+    mapper.apply(&RangeSet::from(x..x + 1)).min().unwrap()
+}
 
-    Ok(())
+pub type U = i64;
+
+/// One almanac map: each source range with the offset that takes it to its
+/// destination. Values outside every source range map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapper {
+    rules: Vec<(RangeSet<U>, U)>,
 }
 
-fn get_mapped_value(mapper: &HashMap<T, U>, x: U) -> U {
-    for k in mapper.keys() {
-        let (low, high) = k;
-        if low <= &x && &x < high {
-            return x - low + mapper.get(k).unwrap();
+impl Mapper {
+    /// Where every value of `values` ends up.
+    pub fn apply(&self, values: &RangeSet<U>) -> RangeSet<U> {
+        let mut unmapped = values.clone();
+        let mut mapped = RangeSet::new();
+
+        for (source, offset) in self.rules.iter() {
+            let hit = unmapped.intersection(source);
+            if !hit.is_empty() {
+                mapped = mapped.union(&hit.shift(*offset));
+                unmapped = unmapped.difference(source);
+            }
         }
+
+        mapped.union(&unmapped)
     }
-    x
 }
-type U = i64;
-type T = (U, U);
 
-fn get_mapper(mappings: &[Vec<U>]) -> HashMap<T, U> {
-    let mut mapper = HashMap::new();
-
-    for v in mappings.iter().take(5) {
-        let (d, s, r) = (v[0], v[1], v[2]);
-        mapper.insert((s, s + r), d);
-    }
-    mapper
+/// Every row of the map takes part. The original version only looked at the
+/// first five rows, which gave 294198454 instead of 535088217 for our input.
+fn get_mapper(mappings: &[Vec<U>]) -> Mapper {
+    let rules = mappings
+        .iter()
+        .map(|v| {
+            let (d, s, r) = (v[0], v[1], v[2]);
+            (RangeSet::from(s..s + r), d - s)
+        })
+        .collect();
+    Mapper { rules }
 }
 
-type PuzzleInput = (Vec<U>, HashMap<String, Vec<Vec<U>>>);
+pub type PuzzleInput = (Vec<U>, HashMap<String, Vec<Vec<U>>>);
 
-fn parse_lines(input: &str) -> PuzzleInput {
-    let xs: Vec<&str> = input.split("\n\n").filter(|s| !s.is_empty()).collect();
+/// The `seeds:` line followed by one `name map:` section per map.
+pub fn parse_lines(input: &str) -> Result<PuzzleInput, ParseError> {
+    let sections = parse::sections(input);
+    let empty = Section { line: 1, text: "" };
+    let first = sections.first().unwrap_or(&empty);
+    let rest = sections.get(1..).unwrap_or_default();
 
-    let seeds: Vec<&str> = xs[0].split(':').filter(|s| !s.is_empty()).collect();
-    assert_eq!(seeds[0], "seeds");
-    let seeds: Vec<U> = seeds[1]
-        .split(&[' ', '\n'])
-        .filter(|s| !s.is_empty())
-        .map(|s| s.parse().unwrap())
-        .collect();
+    let seeds = parse::labelled(first.text, "seeds").map_err(|e| e.offset(first.line - 1))?;
+    let seeds = parse::numbers(seeds).map_err(|e| e.offset(first.line - 1))?;
 
-    let parse_mappings = |txt: &str| {
-        let lines: Vec<&str> = txt.split('\n').filter(|s| !s.is_empty()).collect();
-        let label: Vec<&str> = lines[0].split(' ').filter(|s| !s.is_empty()).collect();
-        let label = label[0].trim().to_owned();
-        let data = lines
-            .iter()
-            .skip(1)
-            .map(|s| {
-                s.split(' ')
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.parse().unwrap())
-                    .collect::<Vec<U>>()
-            })
-            .collect::<Vec<Vec<_>>>();
-        (label, data)
-    };
     let mut maps: HashMap<String, Vec<Vec<U>>> = HashMap::new();
-    for x in xs.iter().skip(1) {
-        let (k, v) = parse_mappings(x);
-        maps.insert(k, v);
+    for section in rest {
+        let mut data = Vec::new();
+        let mut label = "";
+        for (i, line) in section.lines().enumerate() {
+            let at_line = |e: ParseError| e.offset(section.line + i - 1);
+            if i == 0 {
+                let [name] = parse::pattern("{} map:", line).map_err(at_line)?;
+                label = name;
+            } else {
+                let row: Vec<U> = parse::numbers(line).map_err(at_line)?;
+                if row.len() != 3 {
+                    let text = line.trim();
+                    let kind = ParseErrorKind::Invalid {
+                        what: "map row, expected destination, source and length",
+                        text: text.to_owned(),
+                    };
+                    return Err(at_line(ParseError::within(line, text, kind)));
+                }
+                data.push(row);
+            }
+        }
+        maps.insert(label.to_owned(), data);
     }
 
-    (seeds, maps)
+    Ok((seeds, maps))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_short_map_row() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n  52 50\n";
+        let e = parse_lines(input).unwrap_err();
+        assert_eq!((e.line, e.column), (5, 3));
+        assert!(e.to_string().contains("invalid map row"), "{e}");
+
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2 7\n";
+        assert_eq!(parse_lines(input).unwrap_err().line, 4);
+    }
+
+    #[test]
+    fn test_every_map_row_counts() {
+        let rows: Vec<Vec<U>> = (0..7).map(|i| vec![100 + i, i, 1]).collect();
+        let mapper = get_mapper(&rows);
+        assert_eq!(get_mapped_value(&mapper, 4), 104);
+        assert_eq!(get_mapped_value(&mapper, 6), 106);
+        assert_eq!(get_mapped_value(&mapper, 7), 7);
+    }
 }
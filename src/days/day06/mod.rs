@@ -2,8 +2,32 @@ mod puzzle;
 
 use std::io::Error;
 
+use super::solver::Solver;
+
 pub fn main() -> Result<(), Error> {
     puzzle::main();
 
     Ok(())
 }
+
+pub struct Puzzle {
+    times: Vec<i128>,
+    distances: Vec<i128>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (times, distances) = puzzle::parse_races(input)?;
+        Ok(Self { times, distances })
+    }
+
+    fn part1(&self) -> Option<String> {
+        Some(puzzle::margin_of_error(&self.times, &self.distances).to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        let time = puzzle::join_digits(&self.times);
+        let distance = puzzle::join_digits(&self.distances);
+        Some(puzzle::ways_to_win(time, distance).to_string())
+    }
+}
@@ -48,8 +48,9 @@
 /// Idea k(n -k) > m => k^2 - nk + m < 0 or form low = floor((n - sqrt(n^2 - 4m)) / 2) + 1 to n - low
 ///
 
-// use std::fs::File;
-// use std::io::{Error, Read};
+use std::io::{Error, ErrorKind};
+
+use crate::days::{math, parse};
 
 //const INPUT: &str = "puzzle_inputs/day05/sample.input";
 const INPUT: &str = "puzzle_inputs/day05/puzzle.input";
@@ -75,19 +76,44 @@ pub fn main() {
     println!("{w}");
 }
 
-fn ways_to_win(time: i128, disatance: i128) -> i128 {
-    let b = time as f64;
-    let c = disatance as f64;
-    let delta = b * b - 4.0 * c;
-    let x = (b - f64::sqrt(delta)) / 2.0;
+/// The `Time:` and `Distance:` rows of the sheet.
+pub fn parse_races(input: &str) -> Result<(Vec<i128>, Vec<i128>), Error> {
+    let mut rows = Vec::new();
+
+    for (i, (label, line)) in ["Time", "Distance"].iter().zip(input.lines()).enumerate() {
+        let row = parse::labelled(line, label).and_then(parse::numbers);
+        rows.push(row.map_err(|e| e.offset(i))?);
+    }
+
+    match rows.as_slice() {
+        [times, distances] if times.len() == distances.len() => {
+            Ok((times.clone(), distances.clone()))
+        }
+        _ => Err(Error::new(ErrorKind::InvalidData, "malformed race sheet")),
+    }
+}
 
-    let low = if x as i128 == f64::ceil(x) as i128 {
-        (f64::ceil(x) + 1.0) as i128
-    } else {
-        f64::ceil(x) as i128
-    };
+/// Product of the number of ways to win each race.
+pub fn margin_of_error(times: &[i128], distances: &[i128]) -> i128 {
+    times
+        .iter()
+        .zip(distances.iter())
+        .map(|(t, d)| ways_to_win(*t, *d))
+        .product()
+}
 
-    let hi = time - low;
+/// Reads the numbers of a row as the digits of one number, ignoring the
+/// spaces between them.
+pub fn join_digits(xs: &[i128]) -> i128 {
+    let s: String = xs.iter().map(|x| x.to_string()).collect();
+    s.parse().unwrap_or_default()
+}
 
-    hi - low + 1
+/// Number of whole milliseconds `k` to hold the button for that beat the
+/// record, that is with `k * (time - k) > distance`.
+pub fn ways_to_win(time: i128, distance: i128) -> i128 {
+    match math::quadratic_interval(1, -time, distance) {
+        Some((low, high)) => high.min(time) - low.max(0) + 1,
+        None => 0,
+    }
 }
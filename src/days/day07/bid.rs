@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::days::parse::{self, ParseError, ParseErrorKind};
+
+/// A hand of either part together with its bid amount.
+#[derive(Debug)]
+pub struct Bid<H> {
+    pub hand: H,
+    pub amount: i32,
+}
+
+impl<H: fmt::Display> fmt::Display for Bid<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.hand, self.amount)
+    }
+}
+
+/// The hand and bid of every `32T3K 765` line, reading the cards with
+/// `hand`.
+pub fn parse_bids<H, S: AsRef<str>>(
+    lines: &[S],
+    hand: impl Fn(&str) -> Option<H>,
+) -> Result<Vec<Bid<H>>, ParseError> {
+    let mut bids = Vec::new();
+    for (i, l) in lines.iter().enumerate() {
+        bids.push(parse_bid(l.as_ref(), &hand).map_err(|e| e.offset(i))?);
+    }
+    Ok(bids)
+}
+
+fn parse_bid<H>(line: &str, hand: impl Fn(&str) -> Option<H>) -> Result<Bid<H>, ParseError> {
+    let [cards, amount] = parse::pattern("{} {}", line)?;
+    let hand = hand(cards).ok_or_else(|| {
+        let kind = ParseErrorKind::Invalid {
+            what: "hand",
+            text: cards.to_owned(),
+        };
+        ParseError::within(line, cards, kind)
+    })?;
+    let amount: i32 = amount.trim().parse().map_err(|_| {
+        let kind = ParseErrorKind::InvalidNumber(amount.to_owned());
+        ParseError::within(line, amount, kind)
+    })?;
+    Ok(Bid { hand, amount })
+}
+
+/// Sum of the bids, each multiplied by the rank of its hand.
+pub fn total_winnings<H: Ord>(bids: &[Bid<H>]) -> i32 {
+    let mut ranked: Vec<&Bid<H>> = bids.iter().collect();
+    ranked.sort_by(|a, b| a.hand.cmp(&b.hand));
+
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (i + 1) as i32 * b.amount)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day07::{hand, hand_part2};
+
+    const SAMPLE: [&str; 5] = [
+        "32T3K 765",
+        "T55J5 684",
+        "KK677 28",
+        "KTJJT 220",
+        "QQQJA 483",
+    ];
+
+    #[test]
+    fn test_total_winnings() {
+        let bids = parse_bids(&SAMPLE, hand::Hand::from_str).unwrap();
+        assert_eq!(total_winnings(&bids), 6440);
+
+        let bids = parse_bids(&SAMPLE, hand_part2::Hand::from_str).unwrap();
+        assert_eq!(total_winnings(&bids), 5905);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |lines: &[&str]| {
+            parse_bids(lines, hand::Hand::from_str)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error(&["32T3K 765", "KK67X 28"]),
+            "line 2, column 1: invalid hand \"KK67X\""
+        );
+        assert_eq!(
+            error(&["32T3K 7x5"]),
+            "line 1, column 7: invalid number \"7x5\""
+        );
+    }
+}
@@ -1,15 +1,43 @@
 use std::io::Error;
+pub mod bid;
 pub mod card;
-pub mod hand;
 pub mod card_part2;
+pub mod hand;
 pub mod hand_part2;
 
 mod puzzle_part1;
 mod puzzle_part2;
 
+use bid::{parse_bids, total_winnings, Bid};
+
+use super::solver::Solver;
+
 pub fn main() -> Result<(), Error> {
     puzzle_part2::main()?;
     //hand_part2::main();
 
     Ok(())
 }
+
+pub struct Puzzle {
+    bids: Vec<Bid<hand::Hand>>,
+    joker_bids: Vec<Bid<hand_part2::Hand>>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let lines: Vec<&str> = input.lines().collect();
+        Ok(Self {
+            bids: parse_bids(&lines, hand::Hand::from_str)?,
+            joker_bids: parse_bids(&lines, hand_part2::Hand::from_str)?,
+        })
+    }
+
+    fn part1(&self) -> Option<String> {
+        Some(total_winnings(&self.bids).to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        Some(total_winnings(&self.joker_bids).to_string())
+    }
+}
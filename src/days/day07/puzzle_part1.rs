@@ -65,12 +65,11 @@
 // Now, you can determine the total winnings of this set of hands by adding up the result of multiplying each hand's bid with its rank (765 * 1 + 220 * 2 + 28 * 3 + 684 * 4 + 483 * 5). So the total winnings in this example are 6440.
 // Find the rank of every hand in your set. What are the total winnings?
 
-use std::fmt;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Error};
 
+use super::bid::{parse_bids, total_winnings};
 use super::hand::Hand;
-use crate::days::day07::card::Card;
 
 //const INPUT: &str = "puzzle_inputs/day07/sample.input";
 const INPUT: &str = "puzzle_inputs/day07/puzzle.input";
@@ -85,26 +84,8 @@ pub fn main() -> Result<(), Error> {
         v.push(s);
     }
 
-    let mut bids: Vec<Bid> = Vec::new();
-
-    for l in v.iter() {
-        let xs: Vec<_> = l.split(' ').filter(|s| !s.is_empty()).collect();
-        let cards: Option<Vec<_>> = xs[0].chars().map(Card::from_char).collect();
-        let cards = cards.unwrap();
-        let cards = [cards[0], cards[1], cards[2], cards[3], cards[4]];
-        let hand = Hand::new(cards);
-        let amount: i32 = xs[1].parse().unwrap();
-        let bid = Bid((hand, amount));
-        bids.push(bid);
-    }
-
-    bids.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
-
-    let total_bid: i32 = bids
-        .iter()
-        .enumerate()
-        .map(|(i, b)| (i + 1) as i32 * b.0 .1)
-        .sum();
+    let bids = parse_bids(&v, Hand::from_str).expect("malformed hand");
+    let total_bid = total_winnings(&bids);
 
     println!("{}", total_bid);
     //assert_eq!(total_bid, 6592); //part1
@@ -112,13 +93,3 @@ pub fn main() -> Result<(), Error> {
 
     Ok(())
 }
-
-#[derive(Debug)]
-struct Bid((Hand, i32));
-
-impl fmt::Display for Bid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Bid((hand, amout)) = self;
-        write!(f, "({}, {})", hand, amout)
-    }
-}
@@ -1,11 +1,41 @@
+pub mod network;
+
 mod puzzle_part1;
 mod puzzle_part2;
 
 use std::io::Error;
 
+use network::{Network, NodePredicate};
+
+use super::solver::Solver;
+
 pub fn main() -> Result<(), Error> {
     //puzzle_part1::main()?;
     puzzle_part2::main()?;
 
     Ok(())
 }
+
+pub struct Puzzle {
+    network: Network,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let lines: Vec<String> = input.lines().map(|s| s.to_owned()).collect();
+        let network = Network::from_lines(&lines)?;
+        Ok(Self { network })
+    }
+
+    fn part1(&self) -> Option<String> {
+        let start = NodePredicate::exact("AAA");
+        let goal = NodePredicate::exact("ZZZ");
+        self.network.steps(&start, &goal).map(|n| n.to_string())
+    }
+
+    fn part2(&self) -> Option<String> {
+        let start = NodePredicate::suffix("A");
+        let goal = NodePredicate::suffix("Z");
+        self.network.steps(&start, &goal).map(|n| n.to_string())
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::days::graph::{self, Graph};
+use crate::days::math;
+use crate::days::parse::{self, ParseError, ParseErrorKind};
+
+/// Selects nodes of the network by name.
+///
+/// Used both for the nodes a walk starts from and for the nodes it is
+/// trying to reach, so "AAA to ZZZ" and "every ..A to every ..Z" are the
+/// same query with different predicates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodePredicate {
+    /// The node named exactly this.
+    Exact(String),
+    /// Every node whose name ends with this suffix.
+    Suffix(String),
+    /// Every node whose name matches a glob pattern: `?` matches a single
+    /// character and `*` matches any run of characters, e.g. `"1?A"`.
+    Pattern(String),
+    /// Every node named in the set.
+    Set(HashSet<String>),
+}
+
+impl NodePredicate {
+    pub fn exact(name: &str) -> Self {
+        Self::Exact(name.to_owned())
+    }
+
+    pub fn suffix(suffix: &str) -> Self {
+        Self::Suffix(suffix.to_owned())
+    }
+
+    pub fn pattern(pattern: &str) -> Self {
+        Self::Pattern(pattern.to_owned())
+    }
+
+    pub fn set(names: &[&str]) -> Self {
+        Self::Set(names.iter().map(|s| s.to_string()).collect())
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Exact(s) => name == s,
+            Self::Suffix(s) => name.ends_with(s.as_str()),
+            Self::Pattern(p) => glob_match(p.as_bytes(), name.as_bytes()),
+            Self::Set(names) => names.contains(name),
+        }
+    }
+}
+
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+type Map = HashMap<u32, u32>;
+
+/// Most times, modulo the cycle lengths combined so far, at which the walks
+/// of `Network::cycle_steps` can all be on goals that it keeps track of.
+pub const MAX_RESIDUES: usize = 1 << 20;
+
+/// The left/right instructions together with the network of labeled nodes.
+#[derive(Debug)]
+pub struct Network {
+    instructions: Vec<char>,
+    names: Vec<String>,
+    left: Map,
+    right: Map,
+}
+
+impl Network {
+    /// Builds the network from the puzzle lines: the instruction line,
+    /// followed by one `AAA = (BBB, CCC)` line per node. Blank lines are
+    /// ignored.
+    pub fn from_lines(lines: &[String]) -> Result<Self, ParseError> {
+        let invalid = |line: &str, part: &str, what| {
+            let text = part.to_owned();
+            ParseError::within(line, part, ParseErrorKind::Invalid { what, text })
+        };
+
+        let empty = String::new();
+        let (ins, rest) = lines.split_first().unwrap_or((&empty, &[]));
+
+        let instructions: Vec<char> = ins.trim().chars().collect();
+        if instructions.is_empty() || instructions.iter().any(|c| *c != 'L' && *c != 'R') {
+            return Err(invalid(ins, ins, "instruction line"));
+        }
+
+        let mut table = Vec::new();
+        for (i, line) in rest.iter().enumerate() {
+            if !line.is_empty() {
+                let [start, l, r] =
+                    parse::pattern("{} = ({}, {})", line).map_err(|e| e.offset(i + 1))?;
+                table.push((i + 1, line, [start.trim(), l.trim(), r.trim()]));
+            }
+        }
+
+        let names: Vec<String> = table
+            .iter()
+            .map(|(_, _, [n, _, _])| n.to_string())
+            .collect();
+        let node_map: HashMap<&str, u32> = names
+            .iter()
+            .enumerate()
+            .map(|(a, b)| (b.as_str(), a as u32))
+            .collect();
+
+        let mut left = Map::new();
+        let mut right = Map::new();
+        for (i, line, [n, l, r]) in table.iter() {
+            let node = |name: &str| {
+                node_map
+                    .get(name)
+                    .copied()
+                    .ok_or_else(|| invalid(line, name, "node").offset(*i))
+            };
+            let n = node(n)?;
+            left.insert(n, node(l)?);
+            right.insert(n, node(r)?);
+        }
+
+        Ok(Self {
+            instructions,
+            names,
+            left,
+            right,
+        })
+    }
+
+    pub fn name(&self, node: u32) -> &str {
+        &self.names[node as usize]
+    }
+
+    /// Ids of the nodes selected by the predicate, in input order.
+    pub fn nodes_matching(&self, predicate: &NodePredicate) -> Vec<u32> {
+        (0..self.names.len() as u32)
+            .filter(|n| predicate.matches(self.name(*n)))
+            .collect()
+    }
+
+    /// Number of steps needed to get from every node matching `start` to
+    /// nodes matching `goal` at the same time, following the instructions
+    /// from all start nodes simultaneously.
+    ///
+    /// With a single start node this is the plain walk of part one, with
+    /// several it is `cycle_steps`. Returns `None` if no node matches
+    /// `start` or if the walks never stand on goals together.
+    pub fn steps(&self, start: &NodePredicate, goal: &NodePredicate) -> Option<u64> {
+        match self.nodes_matching(start)[..] {
+            [single] => {
+                graph::bfs(self, (single, 0), |(n, _)| goal.matches(self.name(n))).map(|p| p.cost)
+            }
+            _ => self.cycle_steps(start, goal),
+        }
+    }
+
+    /// Same answer as `steps`, found from the cycle every start node falls
+    /// into instead of by walking all of them in lockstep, so that it stays
+    /// fast when the answer runs into the trillions.
+    ///
+    /// Returns `None` if no node matches `start`, if the walks never meet
+    /// on goal nodes, if the answer does not fit, or if there are more than
+    /// `MAX_RESIDUES` ways for the walks to meet to keep track of.
+    pub fn cycle_steps(&self, start: &NodePredicate, goal: &NodePredicate) -> Option<u64> {
+        let is_goal: Vec<bool> = self.names.iter().map(|n| goal.matches(n)).collect();
+
+        let starts = self.nodes_matching(start);
+        if starts.is_empty() {
+            return None;
+        }
+        let orbits: Vec<Orbit> = starts.iter().map(|n| self.orbit(*n, &is_goal)).collect();
+
+        // before every walk is on its cycle, just look
+        let settled = orbits.iter().map(|o| o.offset).max()?;
+        if let Some(t) = (0..settled).find(|t| orbits.iter().all(|o| o.at_goal(*t))) {
+            return Some(t);
+        }
+
+        // afterwards each walk is on a goal exactly when the time matches
+        // one of its cycle hits modulo the cycle length. Add the walks one
+        // at a time, keeping the times modulo the lcm so far at which all
+        // of them are on goals.
+        let mut residues: Vec<i128> = vec![0];
+        let mut modulus: i128 = 1;
+        for orbit in orbits.iter() {
+            let length = orbit.length as i128;
+            let (g, _, _) = math::ext_gcd(modulus, length);
+
+            // only hits in the same class modulo the gcd can agree
+            let mut classes: HashMap<i128, Vec<i128>> = HashMap::new();
+            for hit in orbit.cycle_hits.iter() {
+                let hit = *hit as i128;
+                classes.entry(hit % g).or_default().push(hit);
+            }
+
+            let mut next = Vec::new();
+            for r in residues.iter() {
+                for hit in classes.get(&(r % g)).into_iter().flatten() {
+                    let (x, _) = math::crt(&[(*r, modulus), (*hit, length)])?;
+                    next.push(x);
+                }
+            }
+            next.sort_unstable();
+            next.dedup();
+            if next.is_empty() || next.len() > MAX_RESIDUES {
+                return None;
+            }
+
+            residues = next;
+            modulus = (modulus / g).checked_mul(length)?;
+        }
+
+        let settled = settled as i128;
+        let t = residues
+            .iter()
+            .map(|r| {
+                if *r < settled {
+                    r + (settled - r + modulus - 1) / modulus * modulus
+                } else {
+                    *r
+                }
+            })
+            .min()?;
+        u64::try_from(t).ok()
+    }
+
+    /// Walks from `start` alone until a (node, instruction) state repeats and
+    /// records when it stands on a goal node.
+    fn orbit(&self, start: u32, is_goal: &[bool]) -> Orbit {
+        let period = self.instructions.len();
+        let mut first_seen = HashMap::new();
+        let mut goal_times = Vec::new();
+        let mut current = start;
+        let mut t = 0u64;
+
+        loop {
+            let state = (current, t as usize % period);
+            if let Some(&offset) = first_seen.get(&state) {
+                let (before, cycle_hits) = goal_times.iter().partition(|g| **g < offset);
+                return Orbit {
+                    offset,
+                    length: t - offset,
+                    before,
+                    cycle_hits,
+                };
+            }
+            first_seen.insert(state, t);
+
+            if is_goal[current as usize] {
+                goal_times.push(t);
+            }
+            current = self.next_node(current, self.instructions[state.1]);
+            t += 1;
+        }
+    }
+
+    /// The names of the nodes the walk from `start` passes until it first
+    /// stands on a node matching `goal`, both ends included.
+    pub fn route(&self, start: &str, goal: &NodePredicate) -> Option<Vec<&str>> {
+        let start = self.names.iter().position(|n| n == start)? as u32;
+        let path = graph::bfs(self, (start, 0), |(n, _)| goal.matches(self.name(n)))?;
+        Some(path.nodes.iter().map(|(n, _)| self.name(*n)).collect())
+    }
+
+    fn next_node(&self, current_node: u32, turn: char) -> u32 {
+        match turn {
+            'L' => *self.left.get(&current_node).unwrap(),
+            'R' => *self.right.get(&current_node).unwrap(),
+            _ => panic!("unknown turn {turn}"),
+        }
+    }
+}
+
+/// The walk as a graph whose nodes are a network node together with the
+/// index of the instruction to follow next, each with a single way out.
+impl Graph for Network {
+    type Node = (u32, usize);
+
+    fn neighbours(&self, (node, i): (u32, usize)) -> Vec<((u32, usize), u64)> {
+        let next = self.next_node(node, self.instructions[i]);
+        vec![((next, (i + 1) % self.instructions.len()), 1)]
+    }
+}
+
+/// The walk of one start node: a lead-in of `offset` steps, then a cycle of
+/// `length` steps repeated forever.
+struct Orbit {
+    offset: u64,
+    length: u64,
+    /// Times at which the lead-in stands on a goal.
+    before: Vec<u64>,
+    /// Times within the first pass of the cycle at which it does.
+    cycle_hits: Vec<u64>,
+}
+
+impl Orbit {
+    fn at_goal(&self, t: u64) -> bool {
+        if t < self.offset {
+            self.before.contains(&t)
+        } else {
+            let first = self.offset + (t - self.offset) % self.length;
+            self.cycle_hits.contains(&first)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Walks every start node in lockstep for at most 1000 steps.
+    fn lockstep(net: &Network, start: &NodePredicate, goal: &NodePredicate) -> Option<u64> {
+        let mut current = net.nodes_matching(start);
+        let at_goal = |nodes: &[u32]| nodes.iter().all(|n| goal.matches(net.name(*n)));
+        if current.is_empty() {
+            return None;
+        }
+
+        for (count, turn) in net.instructions.iter().cycle().take(1000).enumerate() {
+            if at_goal(&current) {
+                return Some(count as u64);
+            }
+            for node in current.iter_mut() {
+                *node = net.next_node(*node, *turn);
+            }
+        }
+        None
+    }
+
+    fn network(input: &str) -> Network {
+        let lines: Vec<String> = input.lines().map(|s| s.to_owned()).collect();
+        Network::from_lines(&lines).unwrap()
+    }
+
+    const SAMPLE1: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const SAMPLE2: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const SAMPLE3: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| {
+            let lines: Vec<String> = input.lines().map(|s| s.to_owned()).collect();
+            Network::from_lines(&lines).unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            error("LRX\n\nAAA = (AAA, AAA)"),
+            "line 1, column 1: invalid instruction line \"LRX\""
+        );
+        assert_eq!(
+            error("LR\n\nAAA = (AAA, AAA)\nBBB = (AAA CCC)"),
+            "line 4, column 8: expected \", \""
+        );
+        assert_eq!(
+            error("LR\n\nAAA = (AAA, AAA)\nBBB = (AAA, CCC)"),
+            "line 4, column 13: invalid node \"CCC\""
+        );
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert!(NodePredicate::exact("AAA").matches("AAA"));
+        assert!(!NodePredicate::exact("AAA").matches("AAB"));
+        assert!(NodePredicate::suffix("Z").matches("11Z"));
+        assert!(NodePredicate::pattern("1?A").matches("11A"));
+        assert!(NodePredicate::pattern("*Z").matches("22Z"));
+        assert!(!NodePredicate::pattern("2*B").matches("22C"));
+        assert!(NodePredicate::set(&["11B", "22B"]).matches("22B"));
+    }
+
+    #[test]
+    fn test_part1_samples() {
+        let (aaa, zzz) = (NodePredicate::exact("AAA"), NodePredicate::exact("ZZZ"));
+        assert_eq!(network(SAMPLE1).steps(&aaa, &zzz), Some(2));
+        assert_eq!(network(SAMPLE2).steps(&aaa, &zzz), Some(6));
+    }
+
+    #[test]
+    fn test_part2_sample() {
+        let net = network(SAMPLE3);
+        let (start, goal) = (NodePredicate::suffix("A"), NodePredicate::suffix("Z"));
+        assert_eq!(net.steps(&start, &goal), Some(6));
+        assert_eq!(net.cycle_steps(&start, &goal), Some(6));
+    }
+
+    #[test]
+    fn test_steps_match_lockstep() {
+        let predicates = [
+            NodePredicate::suffix("A"),
+            NodePredicate::suffix("Z"),
+            NodePredicate::suffix("B"),
+            NodePredicate::exact("AAA"),
+            NodePredicate::exact("ZZZ"),
+            NodePredicate::exact("11A"),
+            NodePredicate::exact("22Z"),
+            NodePredicate::pattern("2?C"),
+            NodePredicate::set(&["11Z", "22C"]),
+        ];
+
+        for sample in [SAMPLE1, SAMPLE2, SAMPLE3] {
+            let net = network(sample);
+            for start in predicates.iter() {
+                for goal in predicates.iter() {
+                    let expected = lockstep(&net, start, goal);
+                    assert_eq!(net.steps(start, goal), expected, "{start:?} -> {goal:?}");
+                    assert_eq!(
+                        net.cycle_steps(start, goal),
+                        expected,
+                        "{start:?} -> {goal:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_route() {
+        let net = network(SAMPLE2);
+        let route = net.route("AAA", &NodePredicate::exact("ZZZ")).unwrap();
+        assert_eq!(route, ["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"]);
+        assert_eq!(net.route("ZZZ", &NodePredicate::exact("AAA")), None);
+        assert_eq!(net.route("QQQ", &NodePredicate::exact("AAA")), None);
+    }
+
+    #[test]
+    fn test_walks_that_never_meet() {
+        // both walks reach a goal, but on odd and even steps respectively
+        let net = network(
+            "L
+
+11A = (11Z, 11Z)
+11Z = (11A, 11A)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)",
+        );
+        let (start, goal) = (NodePredicate::suffix("A"), NodePredicate::suffix("Z"));
+        assert_eq!(net.cycle_steps(&start, &goal), None);
+        assert_eq!(net.steps(&start, &goal), None);
+    }
+
+    #[test]
+    fn test_ad_hoc_queries() {
+        let net = network(SAMPLE3);
+        let steps = net.steps(&NodePredicate::exact("22A"), &NodePredicate::pattern("2?C"));
+        assert_eq!(steps, Some(2));
+
+        let unreachable = net.steps(&NodePredicate::exact("11A"), &NodePredicate::exact("22Z"));
+        assert_eq!(unreachable, None);
+
+        let no_start = net.steps(&NodePredicate::exact("QQQ"), &NodePredicate::suffix("Z"));
+        assert_eq!(no_start, None);
+    }
+
+    #[test]
+    fn test_broad_goals_on_real_input() {
+        use std::fs;
+        use std::time::{Duration, Instant};
+
+        let input = fs::read_to_string("puzzle_inputs/day08/puzzle.input").unwrap();
+        let net = network(&input);
+        let goals = net
+            .names
+            .iter()
+            .filter(|n| n.ends_with('Z') || n.starts_with('B'));
+        let goal = NodePredicate::Set(goals.cloned().collect());
+
+        let now = Instant::now();
+        let steps = net.steps(&NodePredicate::suffix("A"), &goal);
+        let elapsed = now.elapsed();
+        assert_eq!(steps, Some(19407849));
+        assert!(elapsed < Duration::from_secs(10), "took {elapsed:?}");
+    }
+}
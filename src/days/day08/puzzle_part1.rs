@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 /// --- Day 8: Haunted Wasteland ---
 ///
 /// You're still riding a camel across Desert Island when you spot a sandstorm quickly approaching.
@@ -49,6 +47,8 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Error};
 
+use super::network::{Network, NodePredicate};
+
 //const INPUT: &str = "puzzle_inputs/day08/sample1.input";
 //const INPUT: &str = "puzzle_inputs/day08/sample2.input";
 const INPUT: &str = "puzzle_inputs/day08/puzzle.input";
@@ -63,89 +63,12 @@ pub fn main() -> Result<(), Error> {
         lines.push(s);
     }
 
-    //let mut nodes = Vec::new();
-
-    let ins = lines[0].trim().to_owned();
-    lines.remove(0);
-    lines.retain(|s| !s.is_empty());
-
-    let table = parse_lines(&lines);
-    let (node_map, left_transition, right_transition) = get_maps(&table);
+    let network = Network::from_lines(&lines).expect("malformed network");
 
-    let start = node_map.get("AAA").unwrap();
-    let goal = node_map.get("ZZZ").unwrap();
-    let steps = steps_taken(*start, *goal, &ins, &left_transition, &right_transition);
+    let start = NodePredicate::exact("AAA");
+    let goal = NodePredicate::exact("ZZZ");
+    let steps = network.steps(&start, &goal).unwrap();
     assert_eq!(steps, 20221);
 
     Ok(())
 }
-
-type Map = HashMap<u32, u32>;
-
-fn steps_taken(start: u32, goal: u32, instr: &str, left: &Map, right: &Map) -> u32 {
-    // println!("start: {} goal: {}, ins: {}", start, goal, instr);
-    // println!();
-    // println!("{:?}", left);
-    // println!();
-    // println!("{:?}", right);
-
-    let mut count = 0;
-    let directions = instr.chars().cycle();
-    let mut current = start;
-
-    let f = |c, s| next_state(s, c, left, right);
-
-    for c in directions {
-        current = f(c, current);
-        count += 1;
-
-        if current == goal {
-            break;
-        }
-    }
-
-    count
-}
-
-fn next_state(current: u32, turn: char, left: &Map, right: &Map) -> u32 {
-    match turn {
-        'L' => *left.get(&current).unwrap(),
-        'R' => *right.get(&current).unwrap(),
-        _ => panic!("unknown turn {turn}"),
-    }
-}
-
-type MapTuple<'a> = (HashMap<&'a str, u32>, Map, Map);
-
-fn get_maps<'a>(table: &'a [(&'a str, Vec<&'a str>)]) -> MapTuple<'a> {
-    let node_str: Vec<&str> = table.iter().map(|(n, _)| *n).collect();
-    let node_map: HashMap<&str, u32> = node_str
-        .iter()
-        .enumerate()
-        .map(|(a, b)| (*b, a as u32))
-        .collect();
-
-    let f = |s, t| (*node_map.get(s).unwrap(), *node_map.get(t).unwrap());
-
-    let left_map: HashMap<_, _> = table.iter().map(|(n, v)| f(*n, v[0])).collect();
-    let right_map: HashMap<_, _> = table.iter().map(|(n, v)| f(*n, v[1])).collect();
-
-    (node_map, left_map, right_map)
-}
-
-fn parse_lines(lines: &[String]) -> Vec<(&str, Vec<&str>)> {
-    let mut buffer: Vec<(&str, Vec<&str>)> = Vec::new();
-    for line in lines {
-        let xs: Vec<&str> = line.split('=').filter(|s| !s.is_empty()).collect();
-
-        let start = xs[0].trim();
-        let next: Vec<&str> = xs[1]
-            .split(&[' ', ',', '(', ')'])
-            .filter(|s| !s.is_empty())
-            .map(|s| s.trim())
-            .collect();
-
-        buffer.push((start, next));
-    }
-    buffer
-}
@@ -1,5 +1,3 @@
-use std::collections::{HashMap, HashSet};
-
 /// --- Part Two ---
 ///
 /// The sandstorm is upon you and you aren't any closer to escaping the wasteland. You had the camel
@@ -49,15 +47,14 @@ use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader, Error};
 
+use super::network::{Network, NodePredicate};
+
 //const INPUT: &str = "puzzle_inputs/day08/sample1.input";
 //const INPUT: &str = "puzzle_inputs/day08/sample2.input";
 const INPUT: &str = "puzzle_inputs/day08/sample3.input";
 
 //const INPUT: &str = "puzzle_inputs/day08/puzzle.input";
 
-type Map = HashMap<u32, u32>;
-type Set = HashSet<u32>;
-
 pub fn main() -> Result<(), Error> {
     let input = File::open(INPUT)?;
     let buffered = BufReader::new(input);
@@ -68,130 +65,14 @@ pub fn main() -> Result<(), Error> {
         lines.push(s);
     }
 
-    //let mut nodes = Vec::new();
-
-    let ins = lines[0].trim().to_owned();
-    lines.remove(0);
-    lines.retain(|s| !s.is_empty());
-
-    let table = parse_lines(&lines);
-    let (node_map, left_transition, right_transition) = get_maps(&table);
-
-    // let start = node_map.get("AAA").unwrap();
-    // let goal = node_map.get("ZZZ").unwrap();
-
-    let rev_node_map: HashMap<u32, &str> = node_map.iter().map(|(n, l)| (*l, *n)).collect();
-    let start: Vec<_> = node_map
-        .iter()
-        .filter(|(s, _)| s.ends_with('A'))
-        .map(|(s, _)| *node_map.get(s).unwrap())
-        .collect();
-
-    // println!("{:?}", node_map);
-    // println!("{:?}", left_transition);
-    // println!("{:?}", right_transition);
-
-    //println!("{:?}", start);
-    //println!("{}", node_map.len());
-    //let start = Vec::from([*node_map.get("AAA").unwrap()]);
+    let network = Network::from_lines(&lines).expect("malformed network");
 
-    let steps = steps_taken(
-        &start,
-        &ins,
-        &rev_node_map,
-        &left_transition,
-        &right_transition,
-    );
+    let start = NodePredicate::suffix("A");
+    let goal = NodePredicate::suffix("Z");
+    let steps = network.steps(&start, &goal).unwrap();
 
     println!("Steps: {}", steps);
-    assert_eq!(steps, 20221);
+    assert_eq!(steps, 6);
 
     Ok(())
 }
-
-fn steps_taken(
-    start: &[u32],
-    instr: &str,
-    rev_node_map: &HashMap<u32, &str>,
-    left: &Map,
-    right: &Map,
-) -> u32 {
-    // println!("start: {} goal: {}, ins: {}", start, goal, instr);
-    // println!();
-    // println!("{:?}", left);
-    // println!();
-    // println!("{:?}", right);
-
-    let mut count = 0;
-    let directions = instr.chars().cycle();
-
-    let mut current: Set = start.iter().cloned().collect();
-
-    let goal_reached = |v: &Vec<u32>| {
-        v.iter().all(|n| {
-            let label = *rev_node_map.get(n).unwrap();
-            label.ends_with('Z')
-        })
-    };
-
-    for turn in directions {
-        let v: Vec<u32> = current.drain().collect();
-
-        if goal_reached(&v) {
-            break;
-        }
-        count += 1;
-
-        for node in v.iter() {
-            current.insert(next_node(*node, turn, left, right));
-        }
-
-        if current.is_empty() {
-            panic!("Current is empty");
-        }
-    }
-    count
-}
-
-fn next_node(current_node: u32, turn: char, left: &Map, right: &Map) -> u32 {
-    match turn {
-        'L' => *left.get(&current_node).unwrap(),
-        'R' => *right.get(&current_node).unwrap(),
-        _ => panic!("unknown turn {turn}"),
-    }
-}
-
-type MapTuple<'a> = (HashMap<&'a str, u32>, Map, Map);
-
-fn get_maps<'a>(table: &'a [(&'a str, Vec<&'a str>)]) -> MapTuple<'a> {
-    let node_str: Vec<&str> = table.iter().map(|(n, _)| *n).collect();
-    let node_map: HashMap<&str, u32> = node_str
-        .iter()
-        .enumerate()
-        .map(|(a, b)| (*b, a as u32))
-        .collect();
-
-    let f = |s, t| (*node_map.get(s).unwrap(), *node_map.get(t).unwrap());
-
-    let left_map: HashMap<u32, u32> = table.iter().map(|(n, v)| f(*n, v[0])).collect();
-    let right_map: HashMap<u32, u32> = table.iter().map(|(n, v)| f(*n, v[1])).collect();
-
-    (node_map, left_map, right_map)
-}
-
-fn parse_lines(lines: &[String]) -> Vec<(&str, Vec<&str>)> {
-    let mut buffer: Vec<(&str, Vec<&str>)> = Vec::new();
-    for line in lines {
-        let xs: Vec<&str> = line.split('=').filter(|s| !s.is_empty()).collect();
-
-        let start = xs[0].trim();
-        let next: Vec<&str> = xs[1]
-            .split(&[' ', ',', '(', ')'])
-            .filter(|s| !s.is_empty())
-            .map(|s| s.trim())
-            .collect();
-
-        buffer.push((start, next));
-    }
-    buffer
-}
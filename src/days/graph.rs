@@ -0,0 +1,409 @@
+//! Graphs and the searches the puzzles keep needing. A graph is anything
+//! that can list the weighted edges leaving a node: an explicit adjacency
+//! list, or a closure computing the neighbours of a state on demand.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    /// The edges leaving `node` as `(target, cost)`.
+    fn neighbours(&self, node: Self::Node) -> Vec<(Self::Node, u64)>;
+}
+
+/// Nodes `0..len` with weighted directed edges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdjacencyList {
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl AdjacencyList {
+    pub fn new(len: usize) -> Self {
+        Self {
+            edges: vec![Vec::new(); len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: u64) {
+        self.edges[from].push((to, cost));
+    }
+
+    /// Adds the edge in both directions.
+    pub fn add_undirected(&mut self, a: usize, b: usize, cost: u64) {
+        self.add_edge(a, b, cost);
+        self.add_edge(b, a, cost);
+    }
+
+    pub fn edges(&self, from: usize) -> &[(usize, u64)] {
+        &self.edges[from]
+    }
+}
+
+impl Graph for AdjacencyList {
+    type Node = usize;
+
+    fn neighbours(&self, node: usize) -> Vec<(usize, u64)> {
+        self.edges[node].clone()
+    }
+}
+
+/// A graph given by a function from a node to its edges, for state spaces
+/// too large or too implicit to build up front.
+pub struct FnGraph<N, F> {
+    f: F,
+    node: PhantomData<fn(N)>,
+}
+
+impl<N, F> FnGraph<N, F>
+where
+    F: Fn(N) -> Vec<(N, u64)>,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            node: PhantomData,
+        }
+    }
+}
+
+impl<N, F> Graph for FnGraph<N, F>
+where
+    N: Copy + Eq + Hash,
+    F: Fn(N) -> Vec<(N, u64)>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: N) -> Vec<(N, u64)> {
+        (self.f)(node)
+    }
+}
+
+/// A route through a graph, both ends included, with its total cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+impl<N> Path<N> {
+    /// Number of edges taken.
+    pub fn len(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn unwind<N: Copy + Eq + Hash>(parent: &HashMap<N, N>, end: N, cost: u64) -> Path<N> {
+    let mut nodes = vec![end];
+    let mut current = end;
+    while let Some(&p) = parent.get(&current) {
+        nodes.push(p);
+        current = p;
+    }
+    nodes.reverse();
+    Path { nodes, cost }
+}
+
+/// Fewest edges from `start` to a node satisfying `is_goal`, ignoring edge
+/// costs; the path's cost is its number of edges.
+pub fn bfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    let mut parent = HashMap::new();
+    let mut depth = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let d = depth[&node];
+        if is_goal(node) {
+            return Some(unwind(&parent, node, d));
+        }
+
+        for (next, _) in graph.neighbours(node) {
+            if let Entry::Vacant(e) = depth.entry(next) {
+                e.insert(d + 1);
+                parent.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Cheapest path from `start` to a node satisfying `is_goal`.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Cheapest path from `start` to a node satisfying `is_goal`, guided by a
+/// `heuristic` that must never overestimate the remaining cost.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(G::Node) -> bool,
+    heuristic: impl Fn(G::Node) -> u64,
+) -> Option<Path<G::Node>> {
+    // the heap holds indices into `nodes`, since nodes need not be ordered
+    let mut nodes = vec![start];
+    let mut index = HashMap::from([(start, 0)]);
+    let mut cost = vec![0u64];
+    let mut parent = HashMap::new();
+    let mut done = vec![false];
+
+    let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0u64, 0usize))]);
+
+    while let Some(Reverse((_, g, i))) = heap.pop() {
+        if done[i] || g > cost[i] {
+            continue;
+        }
+        done[i] = true;
+
+        let node = nodes[i];
+        if is_goal(node) {
+            return Some(unwind(&parent, node, g));
+        }
+
+        for (next, c) in graph.neighbours(node) {
+            let g = g + c;
+            let j = *index.entry(next).or_insert_with(|| {
+                nodes.push(next);
+                cost.push(u64::MAX);
+                done.push(false);
+                nodes.len() - 1
+            });
+
+            if g < cost[j] {
+                cost[j] = g;
+                parent.insert(next, node);
+                heap.push(Reverse((g + heuristic(next), g, j)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Orders the nodes so that every edge goes forwards. `None` if the graph
+/// has a cycle. Among ready nodes, the smallest comes first.
+pub fn topological_sort(graph: &AdjacencyList) -> Option<Vec<usize>> {
+    let mut indegree = vec![0; graph.len()];
+    for from in 0..graph.len() {
+        for (to, _) in graph.edges(from) {
+            indegree[*to] += 1;
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..graph.len())
+        .filter(|n| indegree[*n] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(graph.len());
+
+    while let Some(Reverse(n)) = ready.pop() {
+        order.push(n);
+        for (to, _) in graph.edges(n) {
+            indegree[*to] -= 1;
+            if indegree[*to] == 0 {
+                ready.push(Reverse(*to));
+            }
+        }
+    }
+
+    (order.len() == graph.len()).then_some(order)
+}
+
+/// A maximum flow and the minimum cut that limits it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flow {
+    pub value: u64,
+    /// Nodes still reachable from the source in the residual graph.
+    pub source_side: Vec<bool>,
+    /// Edges from the source side to the sink side, `(from, to)`.
+    pub cut: Vec<(usize, usize)>,
+}
+
+/// Maximum flow from `source` to `sink`, edge costs read as capacities
+/// (Edmonds–Karp).
+pub fn max_flow(graph: &AdjacencyList, source: usize, sink: usize) -> Flow {
+    let n = graph.len();
+
+    // residual edges in pairs: edge e and its reverse e ^ 1
+    let mut to = Vec::new();
+    let mut capacity = Vec::new();
+    let mut out = vec![Vec::new(); n];
+    for from in 0..n {
+        for &(target, c) in graph.edges(from) {
+            out[from].push(to.len());
+            to.push(target);
+            capacity.push(c);
+            out[target].push(to.len());
+            to.push(from);
+            capacity.push(0);
+        }
+    }
+
+    let residual_reach = |capacity: &[u64]| {
+        let mut via = vec![None; n];
+        let mut seen = vec![false; n];
+        seen[source] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for &e in out[u].iter() {
+                let v = to[e];
+                if !seen[v] && capacity[e] > 0 {
+                    seen[v] = true;
+                    via[v] = Some(e);
+                    queue.push_back(v);
+                }
+            }
+        }
+        (seen, via)
+    };
+
+    let mut value = 0;
+    loop {
+        let (seen, via) = residual_reach(&capacity);
+        if source == sink || !seen[sink] {
+            let mut cut = Vec::new();
+            for from in 0..n {
+                for &(target, _) in graph.edges(from) {
+                    if seen[from] && !seen[target] {
+                        cut.push((from, target));
+                    }
+                }
+            }
+            return Flow {
+                value,
+                source_side: seen,
+                cut,
+            };
+        }
+
+        let mut bottleneck = u64::MAX;
+        let mut v = sink;
+        while let Some(e) = via[v] {
+            bottleneck = bottleneck.min(capacity[e]);
+            v = to[e ^ 1];
+        }
+
+        let mut v = sink;
+        while let Some(e) = via[v] {
+            capacity[e] -= bottleneck;
+            capacity[e ^ 1] += bottleneck;
+            v = to[e ^ 1];
+        }
+        value += bottleneck;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0 -> 1 -> 3 costs 1 + 5, 0 -> 2 -> 3 costs 2 + 1.
+    fn diamond() -> AdjacencyList {
+        let mut g = AdjacencyList::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 2);
+        g.add_edge(1, 3, 5);
+        g.add_edge(2, 3, 1);
+        g
+    }
+
+    #[test]
+    fn test_bfs() {
+        let g = diamond();
+        let path = bfs(&g, 0, |n| n == 3).unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 3]);
+        assert_eq!(path.cost, 2);
+        assert_eq!(bfs(&g, 3, |n| n == 0), None);
+        assert!(bfs(&g, 2, |n| n == 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let g = diamond();
+        let path = dijkstra(&g, 0, |n| n == 3).unwrap();
+        assert_eq!(path.nodes, vec![0, 2, 3]);
+        assert_eq!(path.cost, 3);
+
+        // shortest walk on an open grid from (0, 0) to (4, 3), one per step
+        let grid = FnGraph::new(|(x, y): (i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .iter()
+                .map(|(dx, dy)| ((x + dx, y + dy), 1))
+                .filter(|((x, y), _)| (0..10).contains(x) && (0..10).contains(y))
+                .collect()
+        });
+        let goal = (4, 3);
+        let manhattan = |(x, y): (i32, i32)| ((goal.0 - x).abs() + (goal.1 - y).abs()) as u64;
+        let path = astar(&grid, (0, 0), |p| p == goal, manhattan).unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.nodes.last(), Some(&goal));
+        assert_eq!(dijkstra(&grid, (0, 0), |p| p == goal).unwrap().cost, 7);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let g = diamond();
+        assert_eq!(topological_sort(&g), Some(vec![0, 1, 2, 3]));
+
+        let mut cyclic = diamond();
+        cyclic.add_edge(3, 0, 1);
+        assert_eq!(topological_sort(&cyclic), None);
+    }
+
+    #[test]
+    fn test_max_flow() {
+        // the classic CLRS network, max flow 23
+        let mut g = AdjacencyList::new(6);
+        for (a, b, c) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ] {
+            g.add_edge(a, b, c);
+        }
+
+        let flow = max_flow(&g, 0, 5);
+        assert_eq!(flow.value, 23);
+
+        let mut cut = flow.cut.clone();
+        cut.sort();
+        assert_eq!(cut, vec![(1, 3), (4, 3), (4, 5)]);
+        let capacity: u64 = cut
+            .iter()
+            .map(|(a, b)| g.edges(*a).iter().find(|(t, _)| t == b).unwrap().1)
+            .sum();
+        assert_eq!(capacity, flow.value);
+    }
+}
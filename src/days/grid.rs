@@ -0,0 +1,533 @@
+//! A rectangular grid of cells addressed by signed points, for the puzzles
+//! whose input is a picture.
+
+use std::collections::VecDeque;
+use std::error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub};
+
+/// A position or a step on the grid: `x` grows to the right, `y` grows
+/// downwards. Signed so that stepping off the grid is representable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const UP: Self = Self::new(0, -1);
+    pub const DOWN: Self = Self::new(0, 1);
+    pub const LEFT: Self = Self::new(-1, 0);
+    pub const RIGHT: Self = Self::new(1, 0);
+
+    /// Steps to the 4-neighbourhood, clockwise from up.
+    pub const ORTHOGONAL: [Self; 4] = [Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT];
+
+    /// Steps to the 8-neighbourhood, clockwise from up.
+    pub const ALL: [Self; 8] = [
+        Self::UP,
+        Self::new(1, -1),
+        Self::RIGHT,
+        Self::new(1, 1),
+        Self::DOWN,
+        Self::new(-1, 1),
+        Self::LEFT,
+        Self::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, k: i64) -> Self {
+        Self::new(self.x * k, self.y * k)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// No rows, or rows without cells.
+    Empty,
+    /// A row whose width differs from the first row. `line` is one based.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty grid"),
+            Self::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line}: expected {expected} columns, found {found}"),
+        }
+    }
+}
+
+impl error::Error for GridError {}
+
+/// Cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (i, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::RaggedRow {
+                    line: i + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x as i64, y as i64)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        0 <= p.x && p.x < self.width as i64 && 0 <= p.y && p.y < self.height as i64
+    }
+
+    fn index_of(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x as i64, y as i64)))
+    }
+
+    /// Every cell with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points().zip(self.cells.iter())
+    }
+
+    fn steps(&self, p: Point, steps: &'static [Point]) -> impl Iterator<Item = Point> + '_ {
+        steps
+            .iter()
+            .map(move |d| p + *d)
+            .filter(|q| self.contains(*q))
+    }
+
+    /// The in-bounds 4-neighbourhood of `p`.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.steps(p, &Point::ORTHOGONAL)
+    }
+
+    /// The in-bounds 8-neighbourhood of `p`, diagonals included.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.steps(p, &Point::ALL)
+    }
+
+    /// The in-bounds points just outside the rectangle spanned by the two
+    /// corners (inclusive), diagonals included, row by row.
+    pub fn border(&self, top_left: Point, bottom_right: Point) -> impl Iterator<Item = Point> + '_ {
+        let (x1, y1) = (top_left.x - 1, top_left.y - 1);
+        let (x2, y2) = (bottom_right.x + 1, bottom_right.y + 1);
+
+        (y1..=y2)
+            .flat_map(move |y| (x1..=x2).map(move |x| Point::new(x, y)))
+            .filter(move |p| {
+                self.contains(*p) && (p.x == x1 || p.x == x2 || p.y == y1 || p.y == y2)
+            })
+    }
+
+    /// The cells of row `y`.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, top to bottom. Panics if `x` is out of
+    /// bounds, like `row`.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(
+            x < self.width,
+            "column {x} out of bounds, width {}",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells met walking from `start` by `step` until leaving the grid,
+    /// `start` included. `Point::new(1, 1)` walks a diagonal. Panics if
+    /// `step` is zero, as the walk would never leave the grid.
+    pub fn ray(&self, start: Point, step: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        assert_ne!(step, Point::default(), "ray needs a non-zero step");
+        let mut p = start;
+        std::iter::from_fn(move || {
+            let cell = self.get(p)?;
+            let item = (p, cell);
+            p += step;
+            Some(item)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The points 4-connected to `start` whose cells satisfy `include`, in
+    /// breadth-first order. Empty if `start` itself is not included.
+    pub fn flood_fill(&self, start: Point, include: impl Fn(Point, &T) -> bool) -> Vec<Point> {
+        let mut seen = vec![false; self.cells.len()];
+        let mut filled = Vec::new();
+        let mut queue = VecDeque::new();
+
+        if let Some(i) = self.index_of(start) {
+            if include(start, &self.cells[i]) {
+                seen[i] = true;
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(p) = queue.pop_front() {
+            filled.push(p);
+            for q in self.neighbours4(p) {
+                let i = q.y as usize * self.width + q.x as usize;
+                if !seen[i] && include(q, &self.cells[i]) {
+                    seen[i] = true;
+                    queue.push_back(q);
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// Labels the 4-connected regions of cells for which `same` holds
+    /// between neighbours, numbering them from 0 in row by row order of
+    /// their first cell. Returns the labels and the number of regions.
+    pub fn regions(&self, same: impl Fn(&T, &T) -> bool) -> (Grid<usize>, usize) {
+        let mut labels = vec![usize::MAX; self.cells.len()];
+        let mut count = 0;
+
+        for start in self.points() {
+            let i = start.y as usize * self.width + start.x as usize;
+            if labels[i] != usize::MAX {
+                continue;
+            }
+
+            labels[i] = count;
+            let mut stack = vec![start];
+            while let Some(p) = stack.pop() {
+                let cell = &self[p];
+                for q in self.neighbours4(p) {
+                    let j = q.y as usize * self.width + q.x as usize;
+                    if labels[j] == usize::MAX && same(cell, &self.cells[j]) {
+                        labels[j] = count;
+                        stack.push(q);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        let labels = Grid {
+            width: self.width,
+            height: self.height,
+            cells: labels,
+        };
+        (labels, count)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// Rotated a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let h = self.height as i64;
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, h - 1 - p.x)].clone()
+        })
+    }
+
+    /// Rotated a quarter turn counterclockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let w = self.width as i64;
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(w - 1 - p.y, p.x)].clone()
+        })
+    }
+}
+
+impl Grid<char> {
+    /// One row per line, one cell per character.
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, GridError> {
+        Self::from_rows(lines.iter().map(|l| l.as_ref().chars().collect()).collect())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.index_of(p) {
+            Some(i) => &self.cells[i],
+            None => panic!("{p} is outside the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.index_of(p) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{p} is outside the {width}x{height} grid"),
+        }
+    }
+}
+
+/// One line per row. Cells wider than one character are right aligned to
+/// the widest cell and separated by a space.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self.cells.iter().map(|c| c.to_string()).collect();
+        let widest = cells.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+
+        for (y, row) in cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, cell) in row.iter().enumerate() {
+                if widest > 1 {
+                    if x > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{cell:>widest$}")?;
+                } else {
+                    write!(f, "{cell}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid(lines: &[&str]) -> Grid<char> {
+        Grid::from_lines(lines).unwrap()
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[Point::new(2, 1)], 'f');
+        assert_eq!(g.get(Point::new(3, 0)), None);
+        assert_eq!(g.get(Point::new(-1, 0)), None);
+
+        assert_eq!(
+            Grid::from_lines(&["abc", "de"]),
+            Err(GridError::RaggedRow {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(Grid::<char>::from_lines::<&str>(&[]), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = grid(&["123", "456", "789"]);
+        assert_eq!(g.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(g.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(g.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(g.neighbours8(Point::new(1, 2)).count(), 5);
+
+        let border: Vec<char> = g
+            .border(Point::new(0, 1), Point::new(1, 1))
+            .map(|p| g[p])
+            .collect();
+        assert_eq!(border, vec!['1', '2', '3', '6', '7', '8', '9']);
+    }
+
+    #[test]
+    fn test_slices() {
+        let g = grid(&["123", "456", "789"]);
+        assert_eq!(g.row(1), &['4', '5', '6']);
+        assert_eq!(g.column(2).collect::<String>(), "369");
+
+        let diagonal: String = g
+            .ray(Point::new(0, 0), Point::new(1, 1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(diagonal, "159");
+        let anti: String = g
+            .ray(Point::new(2, 0), Point::new(-1, 1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(anti, "357");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of bounds")]
+    fn test_column_out_of_bounds() {
+        let _ = grid(&["123", "456", "789"]).column(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero step")]
+    fn test_ray_without_step() {
+        let _ = grid(&["123"]).ray(Point::new(0, 0), Point::default());
+    }
+
+    #[test]
+    fn test_rotations() {
+        let g = grid(&["abc", "def"]);
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_eq!(g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), g);
+    }
+
+    #[test]
+    fn test_flood_fill_and_regions() {
+        let g = grid(&["..#", ".##", "#.."]);
+        let mut filled = g.flood_fill(Point::new(0, 0), |_, c| *c == '.');
+        filled.sort();
+        assert_eq!(
+            filled,
+            vec![Point::new(0, 0), Point::new(0, 1), Point::new(1, 0)]
+        );
+        assert!(g.flood_fill(Point::new(2, 0), |_, c| *c == '.').is_empty());
+
+        let (labels, count) = g.regions(|a, b| a == b);
+        assert_eq!(count, 4);
+        assert_eq!(labels.to_string(), "001\n011\n233");
+    }
+
+    #[test]
+    fn test_display() {
+        let g = Grid::from_fn(3, 2, |p| p.x * 10 + p.y);
+        assert_eq!(g.to_string(), " 0 10 20\n 1 11 21");
+        assert_eq!(Grid::new(2, 2, '.').to_string(), "..\n..");
+    }
+
+    #[test]
+    fn test_point() {
+        let p = Point::new(2, -3);
+        assert_eq!(p + Point::DOWN * 3, Point::new(2, 0));
+        assert_eq!(-p - Point::LEFT, Point::new(-1, 3));
+        assert_eq!(p.manhattan(Point::default()), 5);
+    }
+}
@@ -0,0 +1,321 @@
+//! Number theory helpers. Everything that can overflow is checked and
+//! reports it as `None` instead of wrapping.
+
+/// Greatest common divisor; `gcd(0, 0)` is 0.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, `None` on overflow. `lcm(0, n)` is 0.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// `gcd` of all the numbers, 0 for none.
+pub fn gcd_all(xs: &[u64]) -> u64 {
+    xs.iter().fold(0, |g, x| gcd(g, *x))
+}
+
+/// `lcm` of all the numbers, 1 for none, `None` on overflow.
+pub fn lcm_all(xs: &[u64]) -> Option<u64> {
+    xs.iter().try_fold(1, |l, x| lcm(l, *x))
+}
+
+/// Extended Euclid: `(g, x, y)` with `a * x + b * y == g == gcd(a, b)` and
+/// `g >= 0`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` is invertible.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base^exp mod m`.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    assert!(m > 0, "modulus must be positive");
+
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Solves the system `x ≡ r (mod m)` for every `(r, m)`, moduli not
+/// necessarily coprime. Returns the smallest solution `x >= 0` and the
+/// modulus of the combined congruence, their lcm. `None` if the system is
+/// inconsistent, a modulus is not positive, or the numbers overflow.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for &(r, n) in congruences {
+        if n <= 0 {
+            return None;
+        }
+
+        // x + m * k ≡ r (mod n)  =>  (m / g) * k ≡ (r - x) / g (mod n / g)
+        let (g, inv, _) = ext_gcd(m, n);
+        let diff = r.checked_sub(x)?;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = n / g;
+        let k = (diff / g)
+            .rem_euclid(step)
+            .checked_mul(inv.rem_euclid(step))?
+            % step;
+        let l = (m / g).checked_mul(n)?;
+
+        x = x.checked_add(m.checked_mul(k)?)?.rem_euclid(l);
+        m = l;
+    }
+
+    Some((x, m))
+}
+
+/// Largest `r` with `r * r <= n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from above never overshoots below the floor
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Integer `floor(a / b)` for `b > 0`.
+fn div_floor(a: i128, b: i128) -> i128 {
+    a.div_euclid(b)
+}
+
+/// The inclusive range of integers `x` with `a * x^2 + b * x + c < 0`, for
+/// `a > 0`. `None` if there is no such integer or the numbers overflow.
+pub fn quadratic_interval(a: i128, b: i128, c: i128) -> Option<(i128, i128)> {
+    assert!(a > 0, "leading coefficient must be positive");
+
+    let disc = b
+        .checked_mul(b)?
+        .checked_sub(a.checked_mul(c)?.checked_mul(4)?)?;
+    if disc <= 0 {
+        return None;
+    }
+    let s = isqrt(disc as u128) as i128;
+
+    let f = |x: i128| -> Option<i128> {
+        a.checked_mul(x)?
+            .checked_add(b)?
+            .checked_mul(x)?
+            .checked_add(c)
+    };
+    let negative = |x: i128| f(x).is_some_and(|v| v < 0);
+
+    // the real roots are (-b ± sqrt(disc)) / 2a; start next to them and
+    // settle on the exact integer bounds
+    let mut lo = div_floor(-b - s, 2 * a);
+    while !negative(lo) && lo <= div_floor(-b, 2 * a) {
+        lo += 1;
+    }
+    while negative(lo - 1) {
+        lo -= 1;
+    }
+
+    let mut hi = div_floor(-b + s, 2 * a) + 1;
+    while !negative(hi) && hi > lo {
+        hi -= 1;
+    }
+    while negative(hi + 1) {
+        hi += 1;
+    }
+
+    (negative(lo) && lo <= hi).then_some((lo, hi))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Deterministic xorshift numbers for the property tests.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(gcd_all(&[12, 18, 27]), 3);
+        assert_eq!(gcd_all(&[]), 0);
+        assert_eq!(lcm_all(&[2, 3, 4]), Some(12));
+        assert_eq!(lcm_all(&[]), Some(1));
+        assert_eq!(lcm_all(&[1 << 40, (1 << 40) - 1]), None);
+    }
+
+    #[test]
+    fn test_gcd_properties() {
+        let mut rng = Rng(0x9e3779b97f4a7c15);
+        for _ in 0..1000 {
+            let (a, b) = (rng.below(1 << 32), rng.below(1 << 32));
+            let g = gcd(a, b);
+            if g > 0 {
+                assert_eq!(a % g, 0);
+                assert_eq!(b % g, 0);
+                assert_eq!(gcd(a / g, b / g), 1);
+                assert_eq!(
+                    lcm(a, b).map(|l| l as u128),
+                    Some(a as u128 * b as u128 / g as u128)
+                );
+            }
+
+            let (a, b) = (a as i128 - (1 << 31), b as i128 - (1 << 31));
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(
+                g as u64,
+                gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+
+        let mut rng = Rng(12345);
+        let p = 1_000_000_007;
+        for _ in 0..200 {
+            let a = rng.below(p - 1) + 1;
+            // Fermat: a^(p-1) = 1 and a^(p-2) is the inverse
+            assert_eq!(mod_pow(a, p - 1, p), 1);
+            assert_eq!(
+                mod_inverse(a as i128, p as i128),
+                Some(mod_pow(a, p - 2, p) as i128)
+            );
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(5, 0)]), None);
+
+        let mut rng = Rng(777);
+        for _ in 0..500 {
+            let n = rng.below(4) as usize + 1;
+            let x = rng.below(1 << 40) as i128;
+            let system: Vec<(i128, i128)> = (0..n)
+                .map(|_| {
+                    let m = rng.below(1000) as i128 + 1;
+                    (x % m, m)
+                })
+                .collect();
+
+            let (y, l) = crt(&system).unwrap();
+            let moduli: Vec<u64> = system.iter().map(|(_, m)| *m as u64).collect();
+            assert_eq!(l as u64, lcm_all(&moduli).unwrap());
+            assert!((0..l).contains(&y));
+            assert_eq!(y, x % l);
+        }
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+
+        let mut rng = Rng(42);
+        for _ in 0..1000 {
+            let n = ((rng.next() as u128) << 64 | rng.next() as u128) >> rng.below(128);
+            let r = isqrt(n);
+            assert!(r * r <= n);
+            assert!((r + 1).checked_mul(r + 1).is_none_or(|s| s > n));
+        }
+    }
+
+    #[test]
+    fn test_quadratic_interval() {
+        // k * (7 - k) > 9
+        assert_eq!(quadratic_interval(1, -7, 9), Some((2, 5)));
+        // k * (30 - k) > 200 has the roots 10 and 20 themselves excluded
+        assert_eq!(quadratic_interval(1, -30, 200), Some((11, 19)));
+        assert_eq!(quadratic_interval(1, 0, 0), None);
+        assert_eq!(quadratic_interval(1, -2, 1), None);
+        assert_eq!(quadratic_interval(4, 0, -1), Some((0, 0)));
+
+        let mut rng = Rng(2023);
+        for _ in 0..500 {
+            let a = rng.below(5) as i128 + 1;
+            let b = rng.below(200) as i128 - 100;
+            let c = rng.below(200) as i128 - 100;
+            let f = |x: i128| a * x * x + b * x + c;
+
+            let brute: Vec<i128> = (-200..=200).filter(|x| f(*x) < 0).collect();
+            let expected = brute.first().map(|lo| (*lo, *brute.last().unwrap()));
+            assert_eq!(quadratic_interval(a, b, c), expected, "{a} {b} {c}");
+        }
+    }
+}
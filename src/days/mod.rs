@@ -5,16 +5,44 @@ mod day02;
 mod day03;
 mod day04;
 mod day05;
+// days 6 and 7 keep their puzzle text as /// comments
+#[allow(clippy::empty_line_after_doc_comments)]
 mod day06;
+#[allow(clippy::empty_line_after_doc_comments)]
 mod day07;
 mod day08;
 mod day09;
 
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod parse;
+pub mod ranges;
+pub mod regression;
+pub mod results;
+pub mod solver;
+
 use std::io::Error;
 
+use solver::Day;
+
 pub fn main() -> Result<(), Error> {
     day09::main();
     //let _ = day05::main();
 
     Ok(())
 }
+
+/// Every day with a solver, in order.
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Puzzle>(1, "puzzle_inputs/day01/puzzle.input"),
+        Day::new::<day02::Puzzle>(2, "puzzle_inputs/day02/puzzle.input"),
+        Day::new::<day03::Puzzle>(3, "puzzle_inputs/day03/puzzle_input"),
+        Day::new::<day04::Puzzle>(4, "puzzle_inputs/day04/puzzle.input"),
+        Day::new::<day05::Puzzle>(5, "puzzle_inputs/day05/puzzle.input"),
+        Day::new::<day06::Puzzle>(6, "puzzle_inputs/day06/puzzle.input"),
+        Day::new::<day07::Puzzle>(7, "puzzle_inputs/day07/puzzle.input"),
+        Day::new::<day08::Puzzle>(8, "puzzle_inputs/day08/puzzle.input"),
+    ]
+}
@@ -0,0 +1,365 @@
+//! Helpers for the bits of input parsing every day needs: numbers, blank
+//! line separated sections, `label:` lines and lines of a fixed shape. All
+//! of them report failures with a one based line and column.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A token that does not parse as the wanted number type.
+    InvalidNumber(String),
+    /// The line does not start with the given label and its ':'.
+    MissingLabel(String),
+    /// Literal text of a pattern that is not where it should be.
+    Expected(String),
+    /// A pattern field with nothing in it.
+    EmptyField,
+    /// Text left over after the end of a pattern.
+    TrailingText(String),
+    /// A field that does not hold the kind of value it should, e.g. a
+    /// `hand` that is not five cards.
+    Invalid { what: &'static str, text: String },
+    /// A value listed a second time where each may only appear once.
+    Duplicate(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// An error on the first line of `s`, at byte offset `at`.
+    fn at(s: &str, at: usize, kind: ParseErrorKind) -> Self {
+        let before = &s[..at];
+        let line = before.matches('\n').count() + 1;
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line,
+            column: before[start..].chars().count() + 1,
+            kind,
+        }
+    }
+
+    /// An error at `part`, a slice of the single `line` it came from.
+    pub fn within(line: &str, part: &str, kind: ParseErrorKind) -> Self {
+        Self::at(line, offset_in(line, part), kind)
+    }
+
+    /// Moves the error down by `lines`, for text that starts on line
+    /// `lines + 1` of the input.
+    pub fn offset(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Moves an error found in `part` to where `part` sits in `line`, the
+    /// single line it is a slice of.
+    pub fn inside(mut self, line: &str, part: &str) -> Self {
+        self.column += line[..offset_in(line, part)].chars().count();
+        self
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumber(s) => write!(f, "invalid number {s:?}"),
+            Self::MissingLabel(s) => write!(f, "expected '{s}:'"),
+            Self::Expected(s) => write!(f, "expected {s:?}"),
+            Self::EmptyField => write!(f, "empty field"),
+            Self::TrailingText(s) => write!(f, "unexpected {s:?} at end of line"),
+            Self::Invalid { what, text } => write!(f, "invalid {what} {text:?}"),
+            Self::Duplicate(s) => write!(f, "{s:?} appears twice"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(e: ParseError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Byte offset of `part` inside `whole`; `part` must be a slice of it.
+fn offset_in(whole: &str, part: &str) -> usize {
+    part.as_ptr() as usize - whole.as_ptr() as usize
+}
+
+/// The whitespace separated numbers of `s`.
+pub fn numbers<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError> {
+    s.split_whitespace()
+        .map(|tok| {
+            tok.parse().map_err(|_| {
+                let kind = ParseErrorKind::InvalidNumber(tok.to_owned());
+                ParseError::at(s, offset_in(s, tok), kind)
+            })
+        })
+        .collect()
+}
+
+/// Every number embedded in `s`, whatever text surrounds it. A '-' right
+/// before the digits is a sign when `T` is signed and a separator when it
+/// is not. Fails on numbers too large for `T`.
+pub fn extract_numbers<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError> {
+    let signed = "-1".parse::<T>().is_ok();
+    let bytes = s.as_bytes();
+
+    let mut v = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let negative = signed && bytes[i] == b'-';
+        let digits = if negative { i + 1 } else { i };
+        if digits >= bytes.len() || !bytes[digits].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut end = digits;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+
+        let tok = &s[i..end];
+        let n = tok
+            .parse()
+            .map_err(|_| ParseError::at(s, i, ParseErrorKind::InvalidNumber(tok.to_owned())))?;
+        v.push(n);
+        i = end;
+    }
+
+    Ok(v)
+}
+
+/// A run of non blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// One based line number of the first line.
+    pub line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines()
+    }
+}
+
+/// Splits `input` at blank lines, dropping the blank lines themselves.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut pos = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let blank = line.trim().is_empty();
+        match (start, blank) {
+            (None, false) => start = Some((i + 1, pos)),
+            (Some((line, begin)), true) => {
+                let text = input[begin..pos].trim_end();
+                sections.push(Section { line, text });
+                start = None;
+            }
+            _ => {}
+        }
+        pos += line.len();
+    }
+    if let Some((line, begin)) = start {
+        let text = input[begin..].trim_end();
+        sections.push(Section { line, text });
+    }
+
+    sections
+}
+
+/// The text after `label:` at the start of `line`.
+pub fn labelled<'a>(line: &'a str, label: &str) -> Result<&'a str, ParseError> {
+    let trimmed = line.trim_start();
+    trimmed
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(|| {
+            let kind = ParseErrorKind::MissingLabel(label.to_owned());
+            ParseError::at(line, offset_in(line, trimmed), kind)
+        })
+}
+
+/// Splits a `key: value` line at its first ':', trimming both sides. A
+/// line without one fails at its end, where the ':' is missing.
+pub fn key_value(line: &str) -> Result<(&str, &str), ParseError> {
+    line.split_once(':')
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .ok_or_else(|| {
+            let kind = ParseErrorKind::Expected(":".to_owned());
+            ParseError::at(line, line.trim_end().len(), kind)
+        })
+}
+
+/// Matches `line` against `pattern`, in which every `{}` stands for a non
+/// empty field running up to the literal text that follows it, and returns
+/// the fields. Trailing whitespace of the line is ignored.
+///
+/// Panics if `pattern` does not hold exactly `N` fields or has two of them
+/// side by side.
+pub fn pattern<'a, const N: usize>(
+    pattern: &str,
+    line: &'a str,
+) -> Result<[&'a str; N], ParseError> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    assert_eq!(
+        literals.len(),
+        N + 1,
+        "pattern {pattern:?} needs {N} fields"
+    );
+
+    let text = line.trim_end();
+    let error = |at: usize, kind| ParseError::at(line, at, kind);
+    let expected = |lit: &str| ParseErrorKind::Expected(lit.to_owned());
+
+    let mut pos = literals[0].len();
+    if !text.starts_with(literals[0]) {
+        return Err(error(0, expected(literals[0])));
+    }
+
+    let mut fields = [""; N];
+    for (i, field) in fields.iter_mut().enumerate() {
+        let next = literals[i + 1];
+        let last = i + 1 == N;
+        assert!(
+            !next.is_empty() || last,
+            "pattern {pattern:?} has adjacent fields"
+        );
+
+        let rest = &text[pos..];
+        let len = if next.is_empty() {
+            rest.len()
+        } else {
+            rest.find(next).ok_or_else(|| error(pos, expected(next)))?
+        };
+        if len == 0 {
+            return Err(error(pos, ParseErrorKind::EmptyField));
+        }
+
+        *field = &rest[..len];
+        pos += len + next.len();
+    }
+
+    if pos < text.len() {
+        let kind = ParseErrorKind::TrailingText(text[pos..].to_owned());
+        return Err(error(pos, kind));
+    }
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers::<u32>(" 41 48  83 "), Ok(vec![41, 48, 83]));
+        assert_eq!(numbers::<i64>("-3 7"), Ok(vec![-3, 7]));
+
+        let e = numbers::<u8>("1 2 300").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 5));
+        assert_eq!(e.to_string(), "line 1, column 5: invalid number \"300\"");
+    }
+
+    #[test]
+    fn test_extract_numbers() {
+        let s = "Card 12: x=-5, y=+7; 10-2";
+        assert_eq!(extract_numbers::<i32>(s), Ok(vec![12, -5, 7, 10, -2]));
+        assert_eq!(extract_numbers::<u32>(s), Ok(vec![12, 5, 7, 10, 2]));
+        assert_eq!(
+            extract_numbers::<u128>("446129210351007"),
+            Ok(vec![446129210351007])
+        );
+
+        let e = extract_numbers::<u8>("a\nb 1000").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "seeds: 1 2\n\n\na map:\n1 2 3\n4 5 6\n  \nb map:\n7 8 9\n";
+        let s = sections(input);
+        assert_eq!(s.len(), 3);
+        assert_eq!((s[0].line, s[0].text), (1, "seeds: 1 2"));
+        assert_eq!((s[1].line, s[1].text), (4, "a map:\n1 2 3\n4 5 6"));
+        assert_eq!(s[2].line, 8);
+        assert_eq!(s[2].lines().count(), 2);
+        assert!(sections("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_labelled() {
+        assert_eq!(labelled("Time:   7  15", "Time"), Ok("   7  15"));
+        assert_eq!(key_value("seeds: 79 14"), Ok(("seeds", "79 14")));
+        assert_eq!(
+            key_value("year 2023 ").unwrap_err().to_string(),
+            "line 1, column 10: expected \":\""
+        );
+
+        let e = labelled("  Distance: 9", "Time").unwrap_err();
+        assert_eq!(e.column, 3);
+        assert_eq!(e.kind, ParseErrorKind::MissingLabel("Time".to_owned()));
+        assert_eq!(e.offset(4).line, 5);
+    }
+
+    #[test]
+    fn test_pattern() {
+        let p = "{} = ({}, {})";
+        assert_eq!(pattern(p, "AAA = (BBB, CCC) "), Ok(["AAA", "BBB", "CCC"]));
+        assert_eq!(pattern("{} {}", "32T3K 765"), Ok(["32T3K", "765"]));
+        assert_eq!(
+            pattern("{} map:", "seed-to-soil map:"),
+            Ok(["seed-to-soil"])
+        );
+
+        let err = |line| {
+            let e = pattern::<3>(p, line).unwrap_err();
+            (e.column, e.kind)
+        };
+        assert_eq!(
+            err("AAA (BBB, CCC)"),
+            (1, ParseErrorKind::Expected(" = (".to_owned()))
+        );
+        assert_eq!(err("AAA = (, CCC)"), (8, ParseErrorKind::EmptyField));
+        assert_eq!(
+            err("AAA = (BBB, CCC) x"),
+            (17, ParseErrorKind::TrailingText(" x".to_owned()))
+        );
+
+        let line = "32T3X 765";
+        let [hand, _] = pattern("{} {}", line).unwrap();
+        let kind = ParseErrorKind::Invalid {
+            what: "hand",
+            text: hand.to_owned(),
+        };
+        let e = ParseError::within(line, hand, kind).offset(2);
+        assert_eq!(e.to_string(), "line 3, column 1: invalid hand \"32T3X\"");
+
+        let line = "7: 1 2x";
+        let (_, list) = line.split_once(':').unwrap();
+        let e = numbers::<u32>(list).unwrap_err().inside(line, list);
+        assert_eq!(e.column, 6);
+    }
+}
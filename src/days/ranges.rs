@@ -0,0 +1,285 @@
+//! Sets of integers stored as sorted, disjoint half-open ranges, for the
+//! puzzles that push whole intervals of numbers around at once.
+
+use std::fmt;
+use std::ops::{Add, Range, Sub};
+
+/// A set of values kept as ranges that are sorted, non-empty, and neither
+/// overlapping nor touching, so every set has exactly one representation.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Builds the set from already normalized ranges.
+    fn normalized(ranges: Vec<Range<T>>) -> Self {
+        debug_assert!(ranges.windows(2).all(|w| w[0].end < w[1].start));
+        Self { ranges }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The largest value is one below this.
+    pub fn end(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end)
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        // the first range ending after x is the only one that can hold it
+        let i = self.ranges.partition_point(|r| r.end <= x);
+        self.ranges.get(i).is_some_and(|r| r.start <= x)
+    }
+
+    /// Adds the values of `range`, merging whatever it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|r| r.end < range.start);
+        let hi = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if lo < hi {
+            range.start.min(self.ranges[lo].start)..range.end.max(self.ranges[hi - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut all: Vec<Range<T>> = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        // merge by start, gluing each range onto the last one if they meet
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.start <= y.start => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+            let Some(r) = next else { break };
+
+            match all.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => all.push(r.clone()),
+            }
+        }
+
+        Self::normalized(all)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut out = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                out.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self::normalized(out)
+    }
+
+    /// The values of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut out = Vec::new();
+        let mut j = 0;
+
+        for r in self.ranges.iter() {
+            let mut start = r.start;
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            // cut out every range of `other` that starts inside what is left
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < r.end {
+                let cut = &other.ranges[k];
+                if start < cut.start {
+                    out.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+                k += 1;
+            }
+            if start < r.end {
+                out.push(start..r.end);
+            }
+        }
+
+        Self::normalized(out)
+    }
+
+    /// Splits into the values below `at` and the values from `at` on.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let i = self.ranges.partition_point(|r| r.end <= at);
+        let mut below = self.ranges[..i].to_vec();
+        let mut above = self.ranges[i..].to_vec();
+
+        if let Some(first) = above.first_mut() {
+            if first.start < at {
+                below.push(first.start..at);
+                first.start = at;
+            }
+        }
+
+        (Self::normalized(below), Self::normalized(above))
+    }
+
+    /// Every value moved by `delta`.
+    pub fn shift<D>(&self, delta: D) -> Self
+    where
+        T: Add<D, Output = T>,
+        D: Copy,
+    {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| r.start + delta..r.end + delta)
+            .collect();
+        Self::normalized(ranges)
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Add<Output = T> + Default,
+    {
+        self.ranges
+            .iter()
+            .fold(T::default(), |n, r| n + (r.end - r.start))
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<Range<T>> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+
+        Self::normalized(merged)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.ranges.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(ranges: &[(i64, i64)]) -> RangeSet<i64> {
+        ranges.iter().map(|(a, b)| *a..*b).collect()
+    }
+
+    fn members(s: &RangeSet<i64>) -> Vec<i64> {
+        (-5..40).filter(|x| s.contains(*x)).collect()
+    }
+
+    #[test]
+    fn test_normalized() {
+        let s = set(&[(10, 12), (0, 3), (2, 5), (5, 7), (20, 20)]);
+        assert_eq!(s.ranges(), &[0..7, 10..12]);
+        assert_eq!((s.min(), s.end(), s.len()), (Some(0), Some(12), 9));
+        assert!(s.contains(6) && !s.contains(7) && !s.contains(-1));
+
+        let mut t = RangeSet::from(0..3);
+        t.insert(10..12);
+        t.insert(3..7);
+        t.insert(8..8);
+        assert_eq!(t, s);
+        t.insert(6..11);
+        assert_eq!(t, RangeSet::from(0..12));
+        assert!(RangeSet::<i64>::new().is_empty());
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set(&[(0, 5), (10, 15), (20, 25)]);
+        let b = set(&[(3, 12), (14, 21), (30, 31)]);
+
+        assert_eq!(a.union(&b).ranges(), &[0..25, 30..31]);
+        assert_eq!(a.intersection(&b).ranges(), &[3..5, 10..12, 14..15, 20..21]);
+        assert_eq!(a.difference(&b).ranges(), &[0..3, 12..14, 21..25]);
+        assert_eq!(b.difference(&a).ranges(), &[5..10, 15..20, 30..31]);
+
+        let (lo, hi) = a.split_at(12);
+        assert_eq!(lo.ranges(), &[0..5, 10..12]);
+        assert_eq!(hi.ranges(), &[12..15, 20..25]);
+        assert_eq!(a.shift(-10).ranges(), &[-10..-5, 0..5, 10..15]);
+    }
+
+    #[test]
+    fn test_operations_match_members() {
+        let sets = [
+            set(&[]),
+            set(&[(0, 1)]),
+            set(&[(0, 5), (10, 15), (20, 25)]),
+            set(&[(3, 12), (14, 21), (30, 31)]),
+            set(&[(-5, 2), (4, 6), (8, 9), (12, 40)]),
+            set(&[(1, 2), (3, 4), (5, 6), (24, 26)]),
+        ];
+
+        for a in sets.iter() {
+            for b in sets.iter() {
+                let (ma, mb) = (members(a), members(b));
+                let union: Vec<i64> = (-5..40)
+                    .filter(|x| ma.contains(x) || mb.contains(x))
+                    .collect();
+                let both: Vec<i64> = ma.iter().copied().filter(|x| mb.contains(x)).collect();
+                let only: Vec<i64> = ma.iter().copied().filter(|x| !mb.contains(x)).collect();
+
+                assert_eq!(members(&a.union(b)), union, "{a:?} | {b:?}");
+                assert_eq!(members(&a.intersection(b)), both, "{a:?} & {b:?}");
+                assert_eq!(members(&a.difference(b)), only, "{a:?} - {b:?}");
+                let collected: RangeSet<i64> =
+                    a.ranges().iter().chain(b.ranges()).cloned().collect();
+                assert_eq!(a.union(b), collected);
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Runs every registered day over the files of `puzzle_inputs/dayNN/` and
+//! compares the answers with the ones recorded in that directory's
+//! `answers` file, one `file part1 part2` line per input, `-` for a part
+//! that is not checked. Inputs without a line are still run, so that parse
+//! errors and panics show up.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use super::registry;
+use super::solver::Day;
+
+pub const ROOT: &str = "puzzle_inputs";
+
+const ANSWERS: &str = "answers";
+
+/// Expected answers of part 1 and part 2.
+pub type Answers = (Option<String>, Option<String>);
+
+/// One input file of a day with its expected answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub day: u32,
+    pub path: PathBuf,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    Read(Error),
+    Parse(Error),
+    Panic(String),
+    Mismatch {
+        part: u32,
+        expected: String,
+        found: Option<String>,
+    },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "cannot read input: {e}"),
+            Self::Parse(e) => write!(f, "cannot parse input: {e}"),
+            Self::Panic(msg) => write!(f, "panicked: {msg}"),
+            Self::Mismatch {
+                part,
+                expected,
+                found,
+            } => {
+                let found = found.as_deref().unwrap_or("no answer");
+                write!(f, "part {part}: expected {expected}, found {found}")
+            }
+        }
+    }
+}
+
+/// What went wrong with one case; empty when it passed.
+#[derive(Debug)]
+pub struct Outcome {
+    pub case: Case,
+    pub failures: Vec<Failure>,
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.passed() { "ok" } else { "FAILED" };
+        write!(
+            f,
+            "day {:02} {}: {status}",
+            self.case.day,
+            self.case.path.display()
+        )?;
+        for failure in self.failures.iter() {
+            write!(f, "\n    {failure}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Expected answers by file name, from the `answers` file of `dir`.
+fn read_answers(dir: &Path) -> Result<HashMap<String, Answers>, Error> {
+    let path = dir.join(ANSWERS);
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let answer = |s: Option<&str>| s.filter(|s| *s != "-").map(|s| s.to_owned());
+
+    let mut answers = HashMap::new();
+    for line in fs::read_to_string(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        if let Some(file) = fields.next() {
+            let part1 = answer(fields.next());
+            let part2 = answer(fields.next());
+            answers.insert(file.to_owned(), (part1, part2));
+        }
+    }
+
+    Ok(answers)
+}
+
+/// The answers recorded for `path` in the `answers` file next to it.
+pub fn expected(path: &Path) -> Result<Answers, Error> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Ok(Answers::default());
+    };
+
+    let mut answers = read_answers(dir)?;
+    Ok(answers
+        .remove(name.to_string_lossy().as_ref())
+        .unwrap_or_default())
+}
+
+/// Every input file under `root/dayNN/` for the given day, sorted by name.
+pub fn cases(root: &Path, day: u32) -> Result<Vec<Case>, Error> {
+    let dir = root.join(format!("day{day:02}"));
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut answers = read_answers(&dir)?;
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    paths.retain(|p| p.is_file() && p.file_name().is_some_and(|n| n != ANSWERS));
+    paths.sort();
+
+    let cases = paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let (part1, part2) = answers.remove(&name).unwrap_or_default();
+            Case {
+                day,
+                path,
+                part1,
+                part2,
+            }
+        })
+        .collect();
+
+    Ok(cases)
+}
+
+/// Solves one case, turning panics into failures instead of unwinding.
+pub fn run(day: &Day, case: Case) -> Outcome {
+    let mut failures = Vec::new();
+
+    match fs::read_to_string(&case.path) {
+        Err(e) => failures.push(Failure::Read(e)),
+        Ok(input) => {
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                day.parse(&input).map(|s| (s.part1(), s.part2()))
+            }));
+
+            match solved {
+                Err(payload) => failures.push(Failure::Panic(panic_message(payload))),
+                Ok(Err(e)) => failures.push(Failure::Parse(e)),
+                Ok(Ok((part1, part2))) => {
+                    let answers = [(1, &case.part1, part1), (2, &case.part2, part2)];
+                    for (part, expected, found) in answers {
+                        if let Some(expected) = expected {
+                            if found.as_ref() != Some(expected) {
+                                failures.push(Failure::Mismatch {
+                                    part,
+                                    expected: expected.clone(),
+                                    found,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Outcome { case, failures }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+/// Runs every case of every registered day.
+pub fn run_all(root: &Path) -> Result<Vec<Outcome>, Error> {
+    let mut outcomes = Vec::new();
+
+    for day in registry() {
+        for case in cases(root, day.number)? {
+            outcomes.push(run(&day, case));
+        }
+    }
+
+    Ok(outcomes)
+}
+
+/// Prints the outcome of every case, failing if any of them did.
+pub fn main() -> Result<(), Error> {
+    let outcomes = run_all(Path::new(ROOT))?;
+    for outcome in outcomes.iter() {
+        println!("{outcome}");
+    }
+
+    let failed = outcomes.iter().filter(|o| !o.passed()).count();
+    println!("{} passed, {failed} failed", outcomes.len() - failed);
+    if failed > 0 {
+        return Err(Error::other(format!("{failed} inputs failed")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_inputs() {
+        let outcomes = run_all(Path::new(ROOT)).unwrap();
+        assert!(!outcomes.is_empty());
+
+        let failed: Vec<String> = outcomes
+            .iter()
+            .filter(|o| !o.passed())
+            .map(|o| o.to_string())
+            .collect();
+        assert!(failed.is_empty(), "\n{}", failed.join("\n"));
+    }
+
+    #[test]
+    fn test_failures_are_reported() {
+        let day = registry().into_iter().find(|d| d.number == 4).unwrap();
+        let case = |path: &str, part1: &str| Case {
+            day: 4,
+            path: PathBuf::from(path),
+            part1: Some(part1.to_owned()),
+            part2: None,
+        };
+
+        let outcome = run(&day, case("puzzle_inputs/day04/sample.input", "13"));
+        assert!(outcome.passed());
+
+        let outcome = run(&day, case("puzzle_inputs/day04/sample.input", "14"));
+        assert_eq!(
+            outcome.failures[0].to_string(),
+            "part 1: expected 14, found 13"
+        );
+
+        let outcome = run(&day, case("puzzle_inputs/day04/missing.input", "13"));
+        assert!(matches!(outcome.failures[0], Failure::Read(_)));
+    }
+}
@@ -0,0 +1,221 @@
+//! Structured record of puzzle runs, written as CSV or as JSON lines so
+//! that outcomes can be tracked over time.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Whether an answer agrees with the one recorded for its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verified {
+    Correct,
+    Wrong,
+    /// No answer is recorded for the input.
+    Unknown,
+}
+
+impl Verified {
+    pub fn check(answer: Option<&str>, expected: Option<&str>) -> Self {
+        match expected {
+            None => Self::Unknown,
+            Some(e) if answer == Some(e) => Self::Correct,
+            Some(_) => Self::Wrong,
+        }
+    }
+}
+
+impl fmt::Display for Verified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::Unknown => "unknown",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The outcome of one part of one day on one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    /// `None` when the part is not solved yet.
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    pub verified: Verified,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    /// One JSON object per line, so that appending keeps the file valid.
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(Self::Csv),
+            "json" | "jsonl" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Guessed from the extension of `path`, CSV unless it says JSON.
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|e| Self::from_name(&e.to_string_lossy()))
+            .unwrap_or(Self::Csv)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Append,
+    Overwrite,
+}
+
+const HEADER: &str = "day,part,input,answer,elapsed_ns,verified";
+
+impl Entry {
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.day,
+            self.part,
+            csv_field(&self.input),
+            self.answer.as_deref().map(csv_field).unwrap_or_default(),
+            self.elapsed.as_nanos(),
+            self.verified
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        let answer = self
+            .answer
+            .as_deref()
+            .map_or("null".to_owned(), json_string);
+        format!(
+            "{{\"day\":{},\"part\":{},\"input\":{},\"answer\":{},\"elapsed_ns\":{},\"verified\":\"{}\"}}",
+            self.day,
+            self.part,
+            json_string(&self.input),
+            answer,
+            self.elapsed.as_nanos(),
+            self.verified
+        )
+    }
+}
+
+/// Writes `entries` to `path`. In append mode a CSV header is only written
+/// when the file is new or empty.
+pub fn write(path: &Path, entries: &[Entry], format: Format, mode: Mode) -> Result<(), Error> {
+    let fresh = match mode {
+        Mode::Overwrite => true,
+        Mode::Append => fs::metadata(path).map_or(true, |m| m.len() == 0),
+    };
+
+    let mut text = String::new();
+    if format == Format::Csv && fresh {
+        text.push_str(HEADER);
+        text.push('\n');
+    }
+    for entry in entries.iter() {
+        match format {
+            Format::Csv => text.push_str(&entry.to_csv()),
+            Format::Json => text.push_str(&entry.to_json()),
+        }
+        text.push('\n');
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(mode == Mode::Append)
+        .truncate(mode == Mode::Overwrite)
+        .open(path)
+        .map_err(|e| Error::other(format!("{}: {e}", path.display())))?;
+    file.write_all(text.as_bytes())
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(part: u32, answer: Option<&str>) -> Entry {
+        Entry {
+            day: 4,
+            part,
+            input: "puzzle_inputs/day04/sample.input".to_owned(),
+            answer: answer.map(|s| s.to_owned()),
+            elapsed: Duration::from_micros(12),
+            verified: Verified::check(answer, Some("13")),
+        }
+    }
+
+    #[test]
+    fn test_formats() {
+        let e = entry(1, Some("13"));
+        assert_eq!(
+            e.to_csv(),
+            "4,1,puzzle_inputs/day04/sample.input,13,12000,correct"
+        );
+        assert_eq!(
+            e.to_json(),
+            "{\"day\":4,\"part\":1,\"input\":\"puzzle_inputs/day04/sample.input\",\
+             \"answer\":\"13\",\"elapsed_ns\":12000,\"verified\":\"correct\"}"
+        );
+
+        let e = entry(2, None);
+        assert_eq!(e.verified, Verified::Wrong);
+        assert!(e.to_json().contains("\"answer\":null"));
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+        assert_eq!(json_string("a\"\\\n"), "\"a\\\"\\\\\\n\"");
+    }
+
+    #[test]
+    fn test_write_modes() {
+        let path = std::env::temp_dir().join(format!("aoc_results_{}.csv", std::process::id()));
+        let entries = [entry(1, Some("13")), entry(2, Some("30"))];
+
+        write(&path, &entries, Format::Csv, Mode::Overwrite).unwrap();
+        write(&path, &entries[..1], Format::Csv, Mode::Append).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text.lines().count(), 4);
+        assert_eq!(text.matches(HEADER).count(), 1);
+
+        write(&path, &entries[..1], Format::Csv, Mode::Overwrite).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Format::from_path(&path), Format::Csv);
+        assert_eq!(Format::from_path(Path::new("results.json")), Format::Json);
+    }
+}
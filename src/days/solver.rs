@@ -0,0 +1,61 @@
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+
+/// A day's puzzle: parsed once from the input text, then solved part by
+/// part. A part that has not been solved yet answers `None`.
+pub trait Solver {
+    fn parse(input: &str) -> Result<Self, Error>
+    where
+        Self: Sized;
+
+    fn part1(&self) -> Option<String>;
+
+    fn part2(&self) -> Option<String>;
+
+    /// How the answer to `part` was worked out, for days that can say.
+    fn trace(&self, _part: u32) -> Option<String> {
+        None
+    }
+
+    /// What changes between the answers of the two parts, for days that can
+    /// say.
+    fn diff(&self) -> Option<String> {
+        None
+    }
+}
+
+type Parser = fn(&str) -> Result<Box<dyn Solver>, Error>;
+
+/// A registered day: its number, the default input file and its solver.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    pub input: &'static str,
+    parser: Parser,
+}
+
+impl Day {
+    pub fn new<S: Solver + 'static>(number: u32, input: &'static str) -> Self {
+        Self {
+            number,
+            input,
+            parser: |s| Ok(Box::new(S::parse(s)?)),
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solver>, Error> {
+        (self.parser)(input)
+    }
+
+    /// Whether the default input file is there; a freshly scaffolded day
+    /// has none until it is fetched.
+    pub fn has_input(&self) -> bool {
+        Path::new(self.input).exists()
+    }
+
+    /// The contents of the default input file.
+    pub fn read_input(&self) -> Result<String, Error> {
+        fs::read_to_string(self.input)
+    }
+}
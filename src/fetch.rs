@@ -0,0 +1,474 @@
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::net::{IpAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::days::parse;
+use crate::days::regression::ROOT;
+
+const USAGE: &str = "usage: fetch DAY [--year Y] [--base-url URL] [--config FILE]";
+
+/// `key: value` lines with `session`, `base_url` and `year`, all optional.
+const CONFIG: &str = "aoc.conf";
+
+const YEAR: u32 = 2023;
+
+/// Where the input comes from and who asks for it. Flags beat the
+/// environment (`AOC_SESSION`, `AOC_BASE_URL`, `AOC_YEAR`), which beats
+/// the config file.
+///
+/// There is no default base URL: only plain HTTP is spoken, and the
+/// session token is only ever sent to a loopback host, so the real site
+/// has to be reached through a local TLS proxy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    pub year: u32,
+}
+
+impl Config {
+    /// Reads the config file, if there is one, then applies `var` over it.
+    pub fn load(path: &Path, var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let mut config = Self {
+            session: None,
+            base_url: None,
+            year: YEAR,
+        };
+
+        if path.exists() {
+            for (i, line) in fs::read_to_string(path)?.lines().enumerate() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    continue;
+                }
+                let (key, value) = parse::key_value(line).map_err(|e| e.offset(i))?;
+                config.set(key, value)?;
+            }
+        }
+
+        for (key, name) in [
+            ("session", "AOC_SESSION"),
+            ("base_url", "AOC_BASE_URL"),
+            ("year", "AOC_YEAR"),
+        ] {
+            if let Some(value) = var(name).filter(|v| !v.is_empty()) {
+                config.set(key, &value)?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let value = value.trim();
+        match key {
+            "session" => self.session = Some(value.to_owned()),
+            "base_url" => self.base_url = Some(value.trim_end_matches('/').to_owned()),
+            "year" => {
+                self.year = value.parse().map_err(|_| {
+                    Error::new(ErrorKind::InvalidData, format!("invalid year {value:?}"))
+                })?
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unknown config key {key:?}"),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    pub fn input_url(&self, day: u32) -> Result<String, Error> {
+        let base_url = self.base_url.as_deref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!(
+                    "no base URL, set AOC_BASE_URL, base_url in {CONFIG} or --base-url \
+                     to a local proxy for the site"
+                ),
+            )
+        })?;
+        Ok(format!("{base_url}/{}/day/{day}/input", self.year))
+    }
+}
+
+/// Whether the input had to be downloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// The input of `day` under `root`, downloading it only if it is not there
+/// yet.
+pub fn fetch(config: &Config, root: &Path, day: u32) -> Result<Fetched, Error> {
+    let path = root.join(format!("day{day:02}")).join("puzzle.input");
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let session = config.session.as_deref().ok_or_else(|| {
+        Error::new(
+            ErrorKind::NotFound,
+            format!("no session token, set AOC_SESSION or session in {CONFIG}"),
+        )
+    })?;
+    let body = get(&config.input_url(day)?, session)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // write next to it first so an interrupted download is never cached
+    let partial = path.with_extension("partial");
+    fs::write(&partial, body)?;
+    fs::rename(&partial, &path)?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Splits `http://host[:port]/path` into the address to connect to, the
+/// host header and the path. Only loopback hosts are accepted, since the
+/// session token would otherwise cross the network in plain text.
+fn split_url(url: &str) -> Result<(String, String, String), Error> {
+    let invalid = |msg: &str| Error::new(ErrorKind::InvalidInput, format!("{msg}: {url}"));
+
+    let rest = url.strip_prefix("http://").ok_or_else(|| {
+        if url.starts_with("https://") {
+            invalid("https is not supported, use an http:// base URL or a local proxy")
+        } else {
+            invalid("expected an http:// URL")
+        }
+    })?;
+
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    if host.is_empty() {
+        return Err(invalid("missing host"));
+    }
+    if !is_loopback(host) {
+        return Err(Error::new(
+            ErrorKind::PermissionDenied,
+            format!(
+                "refusing to send the session token in plain text to {host}, use a local proxy"
+            ),
+        ));
+    }
+
+    let has_port = match host.strip_prefix('[') {
+        Some(rest) => rest.contains("]:"),
+        None => host.contains(':'),
+    };
+    let address = if has_port {
+        host.to_owned()
+    } else {
+        format!("{host}:80")
+    };
+    Ok((address, host.to_owned(), path.to_owned()))
+}
+
+/// Whether `host`, with or without a port, names this machine.
+fn is_loopback(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Body of a `GET` of `url` with the session cookie, failing on anything
+/// but `200 OK`.
+fn get(url: &str, session: &str) -> Result<String, Error> {
+    let (address, host, path) = split_url(url)?;
+
+    let mut stream = TcpStream::connect(&address)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\n\
+         Host: {host}\r\n\
+         Cookie: session={session}\r\n\
+         User-Agent: aoc_2023 fetch\r\n\
+         Connection: close\r\n\r\n"
+    )?;
+
+    let mut reader = BufReader::new(stream);
+    let bad = |msg: String| Error::new(ErrorKind::InvalidData, msg);
+
+    let mut status = String::new();
+    reader.read_line(&mut status)?;
+    let code = status.split_whitespace().nth(1).unwrap_or_default();
+
+    let mut length = None;
+    let mut chunked = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(bad("response ended in the headers".to_owned()));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Ok((name, value)) = parse::key_value(line) {
+            match name.to_ascii_lowercase().as_str() {
+                "content-length" => length = value.parse::<usize>().ok(),
+                "transfer-encoding" => chunked = value.eq_ignore_ascii_case("chunked"),
+                _ => {}
+            }
+        }
+    }
+
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size = String::new();
+            reader.read_line(&mut size)?;
+            let size = size.trim().split(';').next().unwrap_or_default();
+            let size = usize::from_str_radix(size, 16)
+                .map_err(|_| bad(format!("invalid chunk size {size:?}")))?;
+            if size == 0 {
+                break;
+            }
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+            reader.read_line(&mut String::new())?;
+        }
+    } else if let Some(length) = length {
+        body.resize(length, 0);
+        reader.read_exact(&mut body)?;
+    } else {
+        reader.read_to_end(&mut body)?;
+    }
+
+    let body = String::from_utf8(body).map_err(|_| bad("response is not UTF-8".to_owned()))?;
+    if code != "200" {
+        return Err(Error::other(format!(
+            "GET {url} failed: {} {}",
+            status.trim(),
+            body.trim()
+        )));
+    }
+
+    Ok(body)
+}
+
+/// Downloads the input of a day into `puzzle_inputs/` unless it is cached.
+pub fn main(args: &[String]) -> Result<(), Error> {
+    let invalid = |msg: String| Error::new(ErrorKind::InvalidInput, format!("{msg}\n{USAGE}"));
+
+    let mut day = None;
+    let mut config_path = PathBuf::from(CONFIG);
+    let mut overrides = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| invalid(format!("missing value for {arg}")))
+        };
+
+        match arg.as_str() {
+            "--year" | "-y" => overrides.push(("year", value()?.clone())),
+            "--base-url" | "-u" => overrides.push(("base_url", value()?.clone())),
+            "--config" | "-c" => config_path = PathBuf::from(value()?),
+            _ if day.is_none() => {
+                let d: u32 = arg
+                    .parse()
+                    .map_err(|_| invalid(format!("invalid day {arg:?}")))?;
+                day = Some(d);
+            }
+            _ => return Err(invalid(format!("unknown argument {arg:?}"))),
+        }
+    }
+    let day = day.ok_or_else(|| invalid("expected a day".to_owned()))?;
+
+    let mut config = Config::load(&config_path, |name| env::var(name).ok())?;
+    for (key, value) in overrides {
+        config.set(key, &value)?;
+    }
+
+    match fetch(&config, Path::new(ROOT), day)? {
+        Fetched::Cached(path) => println!("{} is already there", path.display()),
+        Fetched::Downloaded(path) => println!("wrote {}", path.display()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves one canned response per connection and hands back the
+    /// requests it got.
+    fn mock_server(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                requests.push(request);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc_fetch_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    fn config(base_url: &str) -> Config {
+        Config {
+            session: Some("53cr3t".to_owned()),
+            base_url: Some(base_url.to_owned()),
+            year: 2023,
+        }
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let body = "0 3 6 9 12 15\n1 3 6 10 15 21\n";
+        let ok = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let (url, server) = mock_server(vec![ok]);
+        let root = temp_root("cache");
+        let config = config(&format!("{url}/aoc"));
+
+        let path = root.join("day09/puzzle.input");
+        assert_eq!(
+            fetch(&config, &root, 9).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), body);
+
+        // the server only answers once, so this must not connect
+        assert_eq!(fetch(&config, &root, 9).unwrap(), Fetched::Cached(path));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /aoc/2023/day/9/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=53cr3t\r\n"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_chunked_and_errors() {
+        let chunked = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                       4\r\n1 2 \r\n2\r\n3\n\r\n0\r\n\r\n";
+        let missing = "HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\n\r\nnot found";
+        let (url, server) = mock_server(vec![chunked.to_owned(), missing.to_owned()]);
+        let root = temp_root("chunked");
+        let config = config(&url);
+
+        fetch(&config, &root, 1).unwrap();
+        let input = fs::read_to_string(root.join("day01/puzzle.input")).unwrap();
+        assert_eq!(input, "1 2 3\n");
+
+        let e = fetch(&config, &root, 2).unwrap_err();
+        assert!(e.to_string().contains("404"), "{e}");
+        assert!(!root.join("day02/puzzle.input").exists());
+        server.join().unwrap();
+
+        let anonymous = Config {
+            session: None,
+            ..config
+        };
+        assert_eq!(
+            fetch(&anonymous, &root, 3).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_config() {
+        let root = temp_root("config");
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join(CONFIG);
+        fs::write(
+            &path,
+            "# my token\nsession: from-file\nbase_url: http://localhost:8080/\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&path, |_| None).unwrap();
+        assert_eq!(config.session.as_deref(), Some("from-file"));
+        assert_eq!(
+            config.input_url(5).unwrap(),
+            "http://localhost:8080/2023/day/5/input"
+        );
+
+        let env = |name: &str| (name == "AOC_SESSION").then(|| "from-env".to_owned());
+        let config = Config::load(&path, env).unwrap();
+        assert_eq!(config.session.as_deref(), Some("from-env"));
+
+        let none = Config::load(&root.join("missing"), |_| None).unwrap();
+        assert_eq!((&none.session, none.year), (&None, YEAR));
+        assert_eq!(none.input_url(1).unwrap_err().kind(), ErrorKind::NotFound);
+
+        fs::write(&path, "colour: blue\n").unwrap();
+        assert!(Config::load(&path, |_| None).is_err());
+        fs::write(&path, "# my token\nsession from-file\n").unwrap();
+        let e = Config::load(&path, |_| None).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 18: expected \":\"");
+        assert!(split_url("https://adventofcode.com/2023").is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_token_stays_local() {
+        for url in [
+            "http://127.0.0.1:8080/x",
+            "http://localhost/",
+            "http://[::1]:80/",
+        ] {
+            assert!(split_url(url).is_ok(), "{url}");
+        }
+        for url in [
+            "http://adventofcode.com/2023",
+            "http://10.0.0.1:8080/",
+            "http://localhost.evil.com/",
+        ] {
+            let e = split_url(url).unwrap_err();
+            assert_eq!(e.kind(), ErrorKind::PermissionDenied, "{url}");
+        }
+
+        // refused before anything is sent
+        let root = temp_root("remote");
+        let e = fetch(&config("http://adventofcode.com"), &root, 1).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::PermissionDenied);
+        assert!(!root.exists());
+    }
+}
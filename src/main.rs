@@ -1,9 +1,29 @@
+mod bench;
 mod days;
+mod fetch;
+mod new_day;
+mod run;
 
-use std::io::Error;
+use std::env;
+use std::io::{Error, ErrorKind};
 
 pub fn main() -> Result<(), Error> {
-    days::main()?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|s| s.as_str()) {
+        None => days::main()?,
+        Some("bench") => bench::main(&args[1..])?,
+        Some("run") => run::main(&args[1..])?,
+        Some("check") => days::regression::main()?,
+        Some("fetch") => fetch::main(&args[1..])?,
+        Some("new-day") => new_day::main(&args[1..])?,
+        Some(cmd) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown command {cmd:?}"),
+            ))
+        }
+    }
 
     Ok(())
 }
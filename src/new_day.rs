@@ -0,0 +1,246 @@
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "usage: new-day N";
+
+const MOD_RS: &str = r#"mod part1;
+mod part2;
+
+use std::io::Error;
+
+use super::solver::Solver;
+
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+impl Solver for Puzzle {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Self {
+            lines: input.lines().map(|s| s.to_owned()).collect(),
+        })
+    }
+
+    fn part1(&self) -> Option<String> {
+        part1::solve(&self.lines)
+    }
+
+    fn part2(&self) -> Option<String> {
+        part2::solve(&self.lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../../puzzle_inputs/day{NN}/sample.input");
+
+    #[test]
+    fn test_sample() {
+        let puzzle = Puzzle::parse(SAMPLE).unwrap();
+        assert_eq!(puzzle.part1(), None);
+        assert_eq!(puzzle.part2(), None);
+    }
+}
+"#;
+
+const PART_RS: &str = r#"// --- Day {N}, part {PART} ---
+
+pub fn solve(_lines: &[String]) -> Option<String> {
+    None
+}
+"#;
+
+const ANSWERS: &str = r#"# file part1 part2, "-" when a part is not checked
+sample.input - -
+"#;
+
+/// The files of a new day under `root`, the directory holding `src/` and
+/// `puzzle_inputs/`, with their contents.
+fn files(root: &Path, day: u32) -> Vec<(PathBuf, String)> {
+    let nn = format!("{day:02}");
+    let fill = |template: &str| {
+        template
+            .replace("{NN}", &nn)
+            .replace("{N}", &day.to_string())
+    };
+
+    let src = root.join("src/days").join(format!("day{nn}"));
+    let inputs = root.join("puzzle_inputs").join(format!("day{nn}"));
+    vec![
+        (src.join("mod.rs"), fill(MOD_RS)),
+        (src.join("part1.rs"), fill(&PART_RS.replace("{PART}", "1"))),
+        (src.join("part2.rs"), fill(&PART_RS.replace("{PART}", "2"))),
+        (inputs.join("sample.input"), String::new()),
+        (inputs.join("answers"), ANSWERS.to_owned()),
+    ]
+}
+
+/// `days/mod.rs` with `dayNN` declared and added to the registry, both in
+/// day order. `None` if the module is already declared.
+fn register(mod_rs: &str, day: u32) -> Option<String> {
+    let module = format!("mod day{day:02};");
+    let entry = format!(
+        "        Day::new::<day{day:02}::Puzzle>({day}, \"puzzle_inputs/day{day:02}/puzzle.input\"),"
+    );
+
+    let mut lines: Vec<String> = mod_rs.lines().map(|s| s.to_owned()).collect();
+    if lines.contains(&module) {
+        return None;
+    }
+
+    // a declared day or registry entry with a number, for ordering
+    let day_of = |line: &str, prefix: &str| -> Option<u32> {
+        let rest = line.trim_start().strip_prefix(prefix)?;
+        rest.get(..2)?.parse().ok()
+    };
+
+    let declared: Vec<usize> = (0..lines.len())
+        .filter(|i| day_of(&lines[*i], "mod day").is_some())
+        .collect();
+    let at = declared
+        .iter()
+        .find(|i| day_of(&lines[**i], "mod day").is_some_and(|d| d > day))
+        .copied()
+        .or(declared.last().map(|i| i + 1))?;
+    lines.insert(at, module);
+
+    let registry = lines.iter().position(|l| l.contains("fn registry()"))?;
+    let end = registry + lines[registry..].iter().position(|l| l.trim() == "]")?;
+    let at = (registry..end)
+        .find(|i| day_of(&lines[*i], "Day::new::<day").is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(at, entry);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Creates the files of `day` under `root` and registers it, refusing to
+/// touch anything if the day already exists. Returns the files written.
+pub fn create(root: &Path, day: u32) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("day {day} is not between 1 and 25"),
+        ));
+    }
+
+    let exists = |what: String| Error::new(ErrorKind::AlreadyExists, what);
+
+    let src = root.join(format!("src/days/day{day:02}"));
+    if src.exists() {
+        return Err(exists(format!("{} already exists", src.display())));
+    }
+
+    let mod_rs = root.join("src/days/mod.rs");
+    let registered = register(&fs::read_to_string(&mod_rs)?, day)
+        .ok_or_else(|| exists(format!("day{day:02} is already declared")))?;
+
+    let files = files(root, day);
+    if let Some((path, _)) = files.iter().find(|(p, _)| p.exists()) {
+        return Err(exists(format!("{} already exists", path.display())));
+    }
+
+    for (path, contents) in files.iter() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)?;
+    }
+    fs::write(&mod_rs, registered)?;
+
+    let mut written: Vec<PathBuf> = files.into_iter().map(|(p, _)| p).collect();
+    written.push(mod_rs);
+    Ok(written)
+}
+
+/// Scaffolds a new day in the current directory.
+pub fn main(args: &[String]) -> Result<(), Error> {
+    let invalid = |msg: String| Error::new(ErrorKind::InvalidInput, format!("{msg}\n{USAGE}"));
+
+    let [day] = args else {
+        return Err(invalid("expected a day".to_owned()));
+    };
+    let day = day
+        .parse()
+        .map_err(|_| invalid(format!("invalid day {day:?}")))?;
+
+    for path in create(Path::new("."), day)? {
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MOD_RS: &str = "mod day01;
+mod day03;
+
+pub mod grid;
+
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Puzzle>(1, \"puzzle_inputs/day01/puzzle.input\"),
+        Day::new::<day03::Puzzle>(3, \"puzzle_inputs/day03/puzzle.input\"),
+    ]
+}
+";
+
+    #[test]
+    fn test_register() {
+        let registered = register(MOD_RS, 2).unwrap();
+        let lines: Vec<&str> = registered.lines().collect();
+        assert_eq!(&lines[..3], ["mod day01;", "mod day02;", "mod day03;"]);
+        assert_eq!(
+            lines[9].trim(),
+            "Day::new::<day02::Puzzle>(2, \"puzzle_inputs/day02/puzzle.input\"),"
+        );
+
+        let last = register(MOD_RS, 12).unwrap();
+        assert!(last.contains("mod day03;\nmod day12;\n"));
+        assert!(last.contains("day03/puzzle.input\"),\n        Day::new::<day12::Puzzle>(12,"));
+
+        assert_eq!(register(MOD_RS, 3), None);
+    }
+
+    #[test]
+    fn test_create() {
+        let root = std::env::temp_dir().join(format!("aoc_new_day_{}", std::process::id()));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), MOD_RS).unwrap();
+
+        let written = create(&root, 10).unwrap();
+        assert_eq!(written.len(), 6);
+        assert!(written.iter().all(|p| p.is_file()));
+
+        let day = fs::read_to_string(root.join("src/days/day10/mod.rs")).unwrap();
+        assert!(day.contains("puzzle_inputs/day10/sample.input"));
+        let part2 = fs::read_to_string(root.join("src/days/day10/part2.rs")).unwrap();
+        assert!(part2.starts_with("// --- Day 10, part 2 ---"));
+
+        // a second run must not overwrite anything
+        fs::write(root.join("src/days/day10/part1.rs"), "solved").unwrap();
+        let e = create(&root, 10).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::AlreadyExists);
+        let part1 = fs::read_to_string(root.join("src/days/day10/part1.rs")).unwrap();
+        assert_eq!(part1, "solved");
+
+        // nor may existing inputs be replaced
+        fs::create_dir_all(root.join("puzzle_inputs/day11")).unwrap();
+        fs::write(root.join("puzzle_inputs/day11/answers"), "mine").unwrap();
+        assert!(create(&root, 11).is_err());
+        assert!(!root.join("src/days/day11").exists());
+
+        assert_eq!(
+            create(&root, 26).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::days::registry;
+use crate::days::regression::expected;
+use crate::days::results::{self, Entry, Format, Mode, Verified};
+
+const USAGE: &str = "usage: run [--day D] [--input FILE] [--results FILE] \
+                     [--format csv|json] [--append | --overwrite] [--trace] [--diff]";
+
+/// Where results go unless `--results` says otherwise.
+const RESULTS: &str = "results.csv";
+
+struct Options {
+    day: Option<u32>,
+    input: Option<String>,
+    results: PathBuf,
+    format: Option<Format>,
+    mode: Mode,
+    /// Print how each answer was worked out, where the day can say.
+    trace: bool,
+    /// Print what changes between the two parts, where the day can say.
+    diff: bool,
+}
+
+impl Options {
+    fn from_args(args: &[String]) -> Result<Self, Error> {
+        let mut options = Self {
+            day: None,
+            input: None,
+            results: PathBuf::from(RESULTS),
+            format: None,
+            mode: Mode::Append,
+            trace: false,
+            diff: false,
+        };
+
+        let invalid = |msg: String| Error::new(ErrorKind::InvalidInput, format!("{msg}\n{USAGE}"));
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| invalid(format!("missing value for {arg}")))
+            };
+
+            match arg.as_str() {
+                "--day" | "-d" => {
+                    let v = value()?;
+                    let day = v
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid day {v:?}")))?;
+                    options.day = Some(day);
+                }
+                "--input" | "-i" => options.input = Some(value()?.clone()),
+                "--results" | "-r" => options.results = PathBuf::from(value()?),
+                "--format" | "-f" => {
+                    let v = value()?;
+                    let format = Format::from_name(v)
+                        .ok_or_else(|| invalid(format!("unknown format {v:?}")))?;
+                    options.format = Some(format);
+                }
+                "--append" => options.mode = Mode::Append,
+                "--overwrite" => options.mode = Mode::Overwrite,
+                "--trace" => options.trace = true,
+                "--diff" => options.diff = true,
+                _ => return Err(invalid(format!("unknown argument {arg:?}"))),
+            }
+        }
+
+        if options.input.is_some() && options.day.is_none() {
+            return Err(invalid("--input needs --day".to_owned()));
+        }
+
+        Ok(options)
+    }
+}
+
+/// Solves every registered day, or the one given, prints the answers and
+/// records them in the results file.
+pub fn main(args: &[String]) -> Result<(), Error> {
+    let options = Options::from_args(args)?;
+
+    let days: Vec<_> = registry()
+        .into_iter()
+        .filter(|d| options.day.is_none_or(|n| n == d.number))
+        .collect();
+    if days.is_empty() {
+        return Err(Error::new(ErrorKind::NotFound, "no such day"));
+    }
+
+    let mut entries = Vec::new();
+
+    for day in days {
+        let input = options.input.as_deref().unwrap_or(day.input);
+        if options.input.is_none() && !day.has_input() {
+            // a freshly scaffolded day without its input yet
+            println!("day {:02}: no input at {input}", day.number);
+            continue;
+        }
+        let (expected1, expected2) = expected(Path::new(input))?;
+        let solver = day.parse(&fs::read_to_string(input)?)?;
+
+        for (part, expected) in [(1, expected1), (2, expected2)] {
+            let now = Instant::now();
+            let answer = match part {
+                1 => solver.part1(),
+                _ => solver.part2(),
+            };
+            let elapsed = now.elapsed();
+
+            let entry = Entry {
+                day: day.number,
+                part,
+                input: input.to_owned(),
+                verified: Verified::check(answer.as_deref(), expected.as_deref()),
+                answer,
+                elapsed,
+            };
+
+            println!(
+                "day {:02} part {part}: {:<16} {:>12?}  {}",
+                entry.day,
+                entry.answer.as_deref().unwrap_or("-"),
+                entry.elapsed,
+                entry.verified
+            );
+            entries.push(entry);
+
+            if options.trace {
+                if let Some(trace) = solver.trace(part) {
+                    println!("{trace}");
+                }
+            }
+        }
+
+        if options.diff {
+            if let Some(diff) = solver.diff() {
+                println!("{diff}");
+            }
+        }
+    }
+
+    let format = options
+        .format
+        .unwrap_or_else(|| Format::from_path(&options.results));
+    results::write(&options.results, &entries, format, options.mode)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|a| a.to_owned()).collect()
+    }
+
+    #[test]
+    fn test_trace_and_diff() {
+        let options = Options::from_args(&args("--day 1 --trace --diff")).unwrap();
+        assert_eq!(options.day, Some(1));
+        assert!(options.trace && options.diff);
+
+        let options = Options::from_args(&args("--day 1")).unwrap();
+        assert!(!options.trace && !options.diff);
+    }
+}